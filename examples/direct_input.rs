@@ -30,6 +30,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             status_code,
             error,
             raw_body,
+            request_id,
         }) => {
             eprintln!("연결 실패 (HTTP {}):", status_code);
             if let Some(error) = error {
@@ -43,6 +44,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             } else {
                 eprintln!("  body: {}", raw_body);
             }
+            if let Some(request_id) = request_id {
+                eprintln!("  request id: {}", request_id);
+            }
         }
         Err(e) => {
             eprintln!("연결 실패: {}. 서버가 실행 중인지 확인하세요.", e);
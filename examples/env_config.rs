@@ -32,6 +32,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             status_code,
             error,
             raw_body,
+            request_id,
         }) => {
             eprintln!("API 오류 (HTTP {}):", status_code);
             if let Some(error) = error {
@@ -45,6 +46,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             } else {
                 eprintln!("  body: {}", raw_body);
             }
+            if let Some(request_id) = request_id {
+                eprintln!("  request id: {}", request_id);
+            }
         }
         Err(e) => {
             eprintln!("오류 발생: {}", e);
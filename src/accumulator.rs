@@ -0,0 +1,480 @@
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::types::{Item, MessageContent, ResponseResource, StreamingEvent};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum AccumulatorError {
+    #[error("`{event_type}` arrived before `response.created`")]
+    MissingResponse { event_type: String },
+
+    #[error("`{event_type}` refers to output item {output_index}, which has not been added")]
+    UnknownOutput { event_type: String, output_index: i32 },
+
+    #[error("`{event_type}` refers to part {index} of output item {output_index}, which has not been added")]
+    UnknownPart {
+        event_type: String,
+        output_index: i32,
+        index: i32,
+    },
+
+    #[error("`{event_type}` does not apply to output item {output_index}")]
+    UnexpectedItem { event_type: String, output_index: i32 },
+
+    #[error("Assembled output item {output_index} does not match the server's: {message}")]
+    Mismatch { output_index: usize, message: String },
+}
+
+/// Rebuilds a [`ResponseResource`] from streaming events.
+///
+/// Deltas are applied to the in-progress output as they arrive, so
+/// [`snapshot`](Self::snapshot) always reflects everything received so far.
+/// On `response.completed` the assembled items are checked against the
+/// server's final payload, which then becomes the result.
+#[derive(Debug, Clone, Default)]
+pub struct StreamAccumulator {
+    /// Shared with the snapshots handed out by [`shared_snapshot`](Self::shared_snapshot);
+    /// copied on write only while one of them is still alive.
+    response: Option<Arc<ResponseResource>>,
+    finished: bool,
+}
+
+impl StreamAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies one event. Returns the final response once a terminal
+    /// `response.*` event has been applied.
+    pub fn apply(&mut self, event: &StreamingEvent) -> Result<Option<&ResponseResource>, AccumulatorError> {
+        match event {
+            StreamingEvent::ResponseCreated { response, .. }
+            | StreamingEvent::ResponseQueued { response, .. }
+            | StreamingEvent::ResponseInProgress { response, .. } => {
+                let output = self.response.take().map(|current| Arc::unwrap_or_clone(current).output);
+                let mut response = response.clone();
+                if let Some(output) = output.filter(|output| !output.is_empty()) {
+                    response.output = output;
+                }
+                self.response = Some(Arc::new(response));
+            }
+            StreamingEvent::ResponseCompleted { response, .. } => {
+                self.finish(response, true)?;
+                return Ok(self.response.as_deref());
+            }
+            StreamingEvent::ResponseFailed { response, .. }
+            | StreamingEvent::ResponseIncomplete { response, .. } => {
+                self.finish(response, false)?;
+                return Ok(self.response.as_deref());
+            }
+            StreamingEvent::OutputItemAdded {
+                output_index,
+                item: Some(item),
+                ..
+            } => {
+                let output = &mut self.response_mut(event)?.output;
+                match usize::try_from(*output_index) {
+                    Ok(index) if index < output.len() => output[index] = item.clone(),
+                    Ok(index) if index == output.len() => output.push(item.clone()),
+                    _ => return Err(unknown_output(event, *output_index)),
+                }
+            }
+            StreamingEvent::OutputItemDone {
+                output_index,
+                item: Some(item),
+                ..
+            } => {
+                let current = self.item_mut(event, *output_index)?;
+                check_item(*output_index as usize, item, current)?;
+                *current = item.clone();
+            }
+            StreamingEvent::ContentPartAdded {
+                output_index,
+                content_index,
+                part,
+                ..
+            } => {
+                let parts = self.content_mut(event, *output_index)?;
+                if !insert_part(parts, *content_index, part.clone()) {
+                    return Err(unknown_part(event, *output_index, *content_index));
+                }
+            }
+            StreamingEvent::ContentPartDone {
+                output_index,
+                content_index,
+                part,
+                ..
+            } => {
+                *self.content_part_mut(event, *output_index, *content_index)? = part.clone();
+            }
+            StreamingEvent::OutputTextDelta {
+                output_index,
+                content_index,
+                delta,
+                logprobs,
+                ..
+            } => match self.content_part_mut(event, *output_index, *content_index)? {
+                MessageContent::OutputText {
+                    text,
+                    logprobs: current,
+                    ..
+                } => {
+                    text.push_str(delta);
+                    if let Some(logprobs) = logprobs {
+                        current.get_or_insert_with(Vec::new).extend(logprobs.iter().cloned());
+                    }
+                }
+                _ => return Err(unexpected_item(event, *output_index)),
+            },
+            StreamingEvent::OutputTextDone {
+                output_index,
+                content_index,
+                text,
+                logprobs,
+                ..
+            } => match self.content_part_mut(event, *output_index, *content_index)? {
+                MessageContent::OutputText {
+                    text: current,
+                    logprobs: current_logprobs,
+                    ..
+                } => {
+                    *current = text.clone();
+                    if logprobs.is_some() {
+                        *current_logprobs = logprobs.clone();
+                    }
+                }
+                _ => return Err(unexpected_item(event, *output_index)),
+            },
+            StreamingEvent::RefusalDelta {
+                output_index,
+                content_index,
+                delta,
+                ..
+            } => match self.content_part_mut(event, *output_index, *content_index)? {
+                MessageContent::Refusal { refusal } => refusal.push_str(delta),
+                _ => return Err(unexpected_item(event, *output_index)),
+            },
+            StreamingEvent::RefusalDone {
+                output_index,
+                content_index,
+                refusal,
+                ..
+            } => match self.content_part_mut(event, *output_index, *content_index)? {
+                MessageContent::Refusal { refusal: current } => *current = refusal.clone(),
+                _ => return Err(unexpected_item(event, *output_index)),
+            },
+            StreamingEvent::ReasoningDelta {
+                output_index,
+                content_index,
+                delta,
+                ..
+            } => self.reasoning_text_mut(event, *output_index, *content_index)?.push_str(delta),
+            StreamingEvent::ReasoningDone {
+                output_index,
+                content_index,
+                text,
+                ..
+            } => *self.reasoning_text_mut(event, *output_index, *content_index)? = text.clone(),
+            StreamingEvent::ReasoningSummaryPartAdded {
+                output_index,
+                summary_index,
+                part,
+                ..
+            } => {
+                let summary = self.summary_mut(event, *output_index)?;
+                if !insert_part(summary, *summary_index, part.clone()) {
+                    return Err(unknown_part(event, *output_index, *summary_index));
+                }
+            }
+            StreamingEvent::ReasoningSummaryPartDone {
+                output_index,
+                summary_index,
+                part,
+                ..
+            } => {
+                *self.summary_part_mut(event, *output_index, *summary_index)? = part.clone();
+            }
+            StreamingEvent::ReasoningSummaryDelta {
+                output_index,
+                summary_index,
+                delta,
+                ..
+            } => match self.summary_part_mut(event, *output_index, *summary_index)? {
+                MessageContent::SummaryText { text } => text.push_str(delta),
+                _ => return Err(unexpected_item(event, *output_index)),
+            },
+            StreamingEvent::ReasoningSummaryDone {
+                output_index,
+                summary_index,
+                text,
+                ..
+            } => match self.summary_part_mut(event, *output_index, *summary_index)? {
+                MessageContent::SummaryText { text: current } => *current = text.clone(),
+                _ => return Err(unexpected_item(event, *output_index)),
+            },
+            StreamingEvent::OutputTextAnnotationAdded {
+                output_index,
+                content_index,
+                annotation_index,
+                annotation: Some(annotation),
+                ..
+            } => match self.content_part_mut(event, *output_index, *content_index)? {
+                MessageContent::OutputText { annotations, .. } => {
+                    let index = usize::try_from(*annotation_index).unwrap_or(usize::MAX);
+                    if index < annotations.len() {
+                        annotations[index] = annotation.clone();
+                    } else {
+                        annotations.push(annotation.clone());
+                    }
+                }
+                _ => return Err(unexpected_item(event, *output_index)),
+            },
+            StreamingEvent::FunctionCallArgumentsDelta {
+                output_index,
+                delta,
+                ..
+            } => match self.item_mut(event, *output_index)? {
+                Item::FunctionCall { arguments, .. } => arguments.push_str(delta),
+                _ => return Err(unexpected_item(event, *output_index)),
+            },
+            StreamingEvent::FunctionCallArgumentsDone {
+                output_index,
+                arguments,
+                ..
+            } => match self.item_mut(event, *output_index)? {
+                Item::FunctionCall {
+                    arguments: current, ..
+                } => *current = arguments.clone(),
+                _ => return Err(unexpected_item(event, *output_index)),
+            },
+            StreamingEvent::OutputItemAdded { item: None, .. }
+            | StreamingEvent::OutputItemDone { item: None, .. }
+            | StreamingEvent::OutputTextAnnotationAdded {
+                annotation: None, ..
+            }
+            | StreamingEvent::Error { .. }
+            | StreamingEvent::Unknown { .. }
+            | StreamingEvent::Done => {}
+        }
+
+        Ok(None)
+    }
+
+    /// The response as assembled so far, or `None` before `response.created`.
+    pub fn snapshot(&self) -> Option<&ResponseResource> {
+        self.response.as_deref()
+    }
+
+    /// Like [`snapshot`](Self::snapshot), but shares the response instead of
+    /// borrowing it. The next event only copies it if the handle is still held.
+    pub fn shared_snapshot(&self) -> Option<Arc<ResponseResource>> {
+        self.response.clone()
+    }
+
+    /// Whether a terminal `response.*` event has been applied.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn into_response(self) -> Option<ResponseResource> {
+        self.response.map(Arc::unwrap_or_clone)
+    }
+
+    /// Adopts the server's final payload. Providers that omit `output` from
+    /// the terminal event keep the assembled items instead. On a mismatch the
+    /// assembled response is kept, so it can still be inspected.
+    fn finish(&mut self, response: &ResponseResource, verify: bool) -> Result<(), AccumulatorError> {
+        if verify && !response.output.is_empty() {
+            let assembled = self.response.as_deref().map_or(&[][..], |current| &current.output);
+            if response.output.len() != assembled.len() {
+                return Err(AccumulatorError::Mismatch {
+                    output_index: assembled.len().min(response.output.len()),
+                    message: format!(
+                        "server returned {} output items, {} were streamed",
+                        response.output.len(),
+                        assembled.len()
+                    ),
+                });
+            }
+            for (index, (expected, actual)) in response.output.iter().zip(assembled).enumerate() {
+                check_item(index, expected, actual)?;
+            }
+        }
+
+        let assembled = self
+            .response
+            .take()
+            .map(|current| Arc::unwrap_or_clone(current).output)
+            .unwrap_or_default();
+        let mut response = response.clone();
+        if response.output.is_empty() {
+            response.output = assembled;
+        }
+
+        self.response = Some(Arc::new(response));
+        self.finished = true;
+        Ok(())
+    }
+
+    fn response_mut(&mut self, event: &StreamingEvent) -> Result<&mut ResponseResource, AccumulatorError> {
+        self.response
+            .as_mut()
+            .map(Arc::make_mut)
+            .ok_or_else(|| AccumulatorError::MissingResponse {
+                event_type: event.event_type().to_string(),
+            })
+    }
+
+    fn item_mut(&mut self, event: &StreamingEvent, output_index: i32) -> Result<&mut Item, AccumulatorError> {
+        let output = &mut self.response_mut(event)?.output;
+        usize::try_from(output_index)
+            .ok()
+            .and_then(|index| output.get_mut(index))
+            .ok_or_else(|| unknown_output(event, output_index))
+    }
+
+    fn content_mut(
+        &mut self,
+        event: &StreamingEvent,
+        output_index: i32,
+    ) -> Result<&mut Vec<MessageContent>, AccumulatorError> {
+        match self.item_mut(event, output_index)? {
+            Item::Message { content, .. } => Ok(content),
+            Item::Reasoning { content, .. } => Ok(content.get_or_insert_with(Vec::new)),
+            _ => Err(unexpected_item(event, output_index)),
+        }
+    }
+
+    fn summary_mut(
+        &mut self,
+        event: &StreamingEvent,
+        output_index: i32,
+    ) -> Result<&mut Vec<MessageContent>, AccumulatorError> {
+        match self.item_mut(event, output_index)? {
+            Item::Reasoning { summary, .. } => Ok(summary),
+            _ => Err(unexpected_item(event, output_index)),
+        }
+    }
+
+    fn content_part_mut(
+        &mut self,
+        event: &StreamingEvent,
+        output_index: i32,
+        content_index: i32,
+    ) -> Result<&mut MessageContent, AccumulatorError> {
+        let parts = self.content_mut(event, output_index)?;
+        part_mut(parts, content_index).ok_or_else(|| unknown_part(event, output_index, content_index))
+    }
+
+    fn summary_part_mut(
+        &mut self,
+        event: &StreamingEvent,
+        output_index: i32,
+        summary_index: i32,
+    ) -> Result<&mut MessageContent, AccumulatorError> {
+        let parts = self.summary_mut(event, output_index)?;
+        part_mut(parts, summary_index).ok_or_else(|| unknown_part(event, output_index, summary_index))
+    }
+
+    /// Reasoning text parts are created on first use, since not every provider
+    /// announces them with `response.content_part.added`.
+    fn reasoning_text_mut(
+        &mut self,
+        event: &StreamingEvent,
+        output_index: i32,
+        content_index: i32,
+    ) -> Result<&mut String, AccumulatorError> {
+        let parts = self.content_mut(event, output_index)?;
+        if usize::try_from(content_index).ok() == Some(parts.len()) {
+            parts.push(MessageContent::reasoning(""));
+        }
+
+        match part_mut(parts, content_index) {
+            Some(MessageContent::ReasoningText { text }) => Ok(text),
+            Some(_) => Err(unexpected_item(event, output_index)),
+            None => Err(unknown_part(event, output_index, content_index)),
+        }
+    }
+}
+
+fn part_mut(parts: &mut [MessageContent], index: i32) -> Option<&mut MessageContent> {
+    usize::try_from(index).ok().and_then(|index| parts.get_mut(index))
+}
+
+/// Returns `false` when `index` would leave a gap.
+fn insert_part(parts: &mut Vec<MessageContent>, index: i32, part: MessageContent) -> bool {
+    match usize::try_from(index) {
+        Ok(index) if index < parts.len() => parts[index] = part,
+        Ok(index) if index == parts.len() => parts.push(part),
+        _ => return false,
+    }
+    true
+}
+
+/// Compares the parts of an item that are assembled from deltas.
+fn check_item(output_index: usize, expected: &Item, actual: &Item) -> Result<(), AccumulatorError> {
+    let expected_text = assembled_text(expected);
+    let actual_text = assembled_text(actual);
+
+    if expected_text.is_some() && actual_text.is_some() && expected_text != actual_text {
+        return Err(AccumulatorError::Mismatch {
+            output_index,
+            message: format!("expected {expected_text:?}, assembled {actual_text:?}"),
+        });
+    }
+
+    Ok(())
+}
+
+fn assembled_text(item: &Item) -> Option<Vec<&str>> {
+    match item {
+        Item::Message { content, .. } => Some(content.iter().filter_map(part_text).collect()),
+        Item::FunctionCall {
+            name, arguments, ..
+        } => Some(vec![name.as_str(), arguments.as_str()]),
+        Item::Reasoning {
+            summary, content, ..
+        } => Some(
+            summary
+                .iter()
+                .chain(content.iter().flatten())
+                .filter_map(part_text)
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+fn part_text(part: &MessageContent) -> Option<&str> {
+    match part {
+        MessageContent::OutputText { text, .. }
+        | MessageContent::InputText { text }
+        | MessageContent::PlainText { text }
+        | MessageContent::SummaryText { text }
+        | MessageContent::ReasoningText { text } => Some(text),
+        MessageContent::Refusal { refusal } => Some(refusal),
+        _ => None,
+    }
+}
+
+fn unknown_output(event: &StreamingEvent, output_index: i32) -> AccumulatorError {
+    AccumulatorError::UnknownOutput {
+        event_type: event.event_type().to_string(),
+        output_index,
+    }
+}
+
+fn unknown_part(event: &StreamingEvent, output_index: i32, index: i32) -> AccumulatorError {
+    AccumulatorError::UnknownPart {
+        event_type: event.event_type().to_string(),
+        output_index,
+        index,
+    }
+}
+
+fn unexpected_item(event: &StreamingEvent, output_index: i32) -> AccumulatorError {
+    AccumulatorError::UnexpectedItem {
+        event_type: event.event_type().to_string(),
+        output_index,
+    }
+}
@@ -0,0 +1,362 @@
+use async_trait::async_trait;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue};
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+const API_KEY_HEADER: &str = "api-key";
+
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("Invalid credential: {0}")]
+    InvalidCredential(String),
+
+    #[error("Failed to read token file `{path}`: {source}")]
+    TokenFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Token command failed: {0}")]
+    TokenCommand(String),
+}
+
+/// Supplies the credentials attached to each API call.
+///
+/// `headers` is awaited before every attempt, including retries and stream
+/// reconnects, so implementations can rotate credentials without rebuilding
+/// the client. After a `401`, `invalidate` is called and the request is sent
+/// once more with fresh headers.
+#[async_trait]
+pub trait AuthProvider: Send + Sync {
+    async fn headers(&self) -> Result<HeaderMap, AuthError>;
+
+    /// Called when the server rejects a request with `401 Unauthorized`.
+    async fn invalidate(&self) {}
+}
+
+#[async_trait]
+impl<T: AuthProvider + ?Sized> AuthProvider for Arc<T> {
+    async fn headers(&self) -> Result<HeaderMap, AuthError> {
+        (**self).headers().await
+    }
+
+    async fn invalidate(&self) {
+        (**self).invalidate().await
+    }
+}
+
+/// `Authorization: Bearer <token>`, the default for every client.
+#[derive(Debug, Clone)]
+pub struct BearerAuth {
+    token: String,
+}
+
+impl BearerAuth {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self { token: token.into() }
+    }
+}
+
+#[async_trait]
+impl AuthProvider for BearerAuth {
+    async fn headers(&self) -> Result<HeaderMap, AuthError> {
+        bearer_headers(&self.token)
+    }
+}
+
+/// Sends the key in a plain header, `api-key` by default (Azure OpenAI style).
+#[derive(Debug, Clone)]
+pub struct ApiKeyAuth {
+    header: String,
+    key: String,
+}
+
+impl ApiKeyAuth {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self::with_header(API_KEY_HEADER, key)
+    }
+
+    pub fn with_header(header: impl Into<String>, key: impl Into<String>) -> Self {
+        Self {
+            header: header.into(),
+            key: key.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl AuthProvider for ApiKeyAuth {
+    async fn headers(&self) -> Result<HeaderMap, AuthError> {
+        let name = HeaderName::from_bytes(self.header.as_bytes())
+            .map_err(|_| AuthError::InvalidCredential(format!("invalid header name `{}`", self.header)))?;
+        let mut value = HeaderValue::from_str(&self.key)
+            .map_err(|_| AuthError::InvalidCredential(format!("invalid value for `{}`", self.header)))?;
+        value.set_sensitive(true);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(name, value);
+        Ok(headers)
+    }
+}
+
+/// Sends no credentials, for local servers such as LM Studio or Ollama.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoAuth;
+
+#[async_trait]
+impl AuthProvider for NoAuth {
+    async fn headers(&self) -> Result<HeaderMap, AuthError> {
+        Ok(HeaderMap::new())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessToken {
+    pub token: String,
+    pub expires_in: Option<Duration>,
+}
+
+impl AccessToken {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            expires_in: None,
+        }
+    }
+
+    pub fn expires_in(mut self, expires_in: Duration) -> Self {
+        self.expires_in = Some(expires_in);
+        self
+    }
+
+    /// Accepts either a bare token or an OAuth-style JSON object with
+    /// `access_token` and an optional `expires_in` in seconds.
+    pub fn parse(output: &str) -> Result<Self, AuthError> {
+        #[derive(Deserialize)]
+        struct TokenResponse {
+            access_token: String,
+            expires_in: Option<u64>,
+        }
+
+        let output = output.trim();
+        if output.starts_with('{') {
+            let response: TokenResponse = serde_json::from_str(output)
+                .map_err(|error| AuthError::InvalidCredential(format!("invalid token JSON: {error}")))?;
+            return Ok(Self {
+                token: response.access_token,
+                expires_in: response.expires_in.map(Duration::from_secs),
+            });
+        }
+
+        if output.is_empty() {
+            return Err(AuthError::InvalidCredential("token is empty".to_string()));
+        }
+        Ok(Self::new(output))
+    }
+}
+
+/// Where [`RefreshingTokenAuth`] gets fresh tokens from.
+#[async_trait]
+pub trait TokenSource: Send + Sync {
+    async fn fetch(&self) -> Result<AccessToken, AuthError>;
+}
+
+/// Reads the token from a file, re-reading it on every refresh.
+#[derive(Debug, Clone)]
+pub struct FileTokenSource {
+    path: PathBuf,
+}
+
+impl FileTokenSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl TokenSource for FileTokenSource {
+    async fn fetch(&self) -> Result<AccessToken, AuthError> {
+        let contents = tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(|source| AuthError::TokenFile {
+                path: self.path.clone(),
+                source,
+            })?;
+        AccessToken::parse(&contents)
+    }
+}
+
+/// Runs a command and uses its standard output as the token.
+#[derive(Debug, Clone)]
+pub struct CommandTokenSource {
+    program: String,
+    args: Vec<String>,
+}
+
+impl CommandTokenSource {
+    pub fn new(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+}
+
+#[async_trait]
+impl TokenSource for CommandTokenSource {
+    async fn fetch(&self) -> Result<AccessToken, AuthError> {
+        let output = tokio::process::Command::new(&self.program)
+            .args(&self.args)
+            .output()
+            .await
+            .map_err(|error| AuthError::TokenCommand(format!("`{}`: {error}", self.program)))?;
+
+        if !output.status.success() {
+            return Err(AuthError::TokenCommand(format!(
+                "`{}` exited with {}: {}",
+                self.program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        AccessToken::parse(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// Bearer auth with a cached token that is refreshed from a [`TokenSource`]
+/// shortly before it expires, or after the server answers `401`.
+pub struct RefreshingTokenAuth {
+    source: Box<dyn TokenSource>,
+    default_ttl: Duration,
+    refresh_margin: Duration,
+    cached: Mutex<Option<CachedToken>>,
+}
+
+struct CachedToken {
+    headers: HeaderMap,
+    refresh_at: Instant,
+}
+
+impl RefreshingTokenAuth {
+    pub fn new(source: impl TokenSource + 'static) -> Self {
+        Self {
+            source: Box::new(source),
+            default_ttl: Duration::from_secs(300),
+            refresh_margin: Duration::from_secs(30),
+            cached: Mutex::new(None),
+        }
+    }
+
+    pub fn from_file(path: impl Into<PathBuf>) -> Self {
+        Self::new(FileTokenSource::new(path))
+    }
+
+    /// How long to keep a token whose source did not report `expires_in`.
+    pub fn default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    /// How long before expiry a token is refreshed.
+    pub fn refresh_margin(mut self, margin: Duration) -> Self {
+        self.refresh_margin = margin;
+        self
+    }
+}
+
+#[async_trait]
+impl AuthProvider for RefreshingTokenAuth {
+    async fn headers(&self) -> Result<HeaderMap, AuthError> {
+        let mut cached = self.cached.lock().await;
+        if let Some(token) = cached.as_ref().filter(|token| Instant::now() < token.refresh_at) {
+            return Ok(token.headers.clone());
+        }
+
+        let token = self.source.fetch().await?;
+        let ttl = token.expires_in.unwrap_or(self.default_ttl);
+        let headers = bearer_headers(&token.token)?;
+        *cached = Some(CachedToken {
+            headers: headers.clone(),
+            refresh_at: Instant::now() + ttl.saturating_sub(self.refresh_margin),
+        });
+        Ok(headers)
+    }
+
+    async fn invalidate(&self) {
+        self.cached.lock().await.take();
+    }
+}
+
+pub(crate) fn bearer_headers(token: &str) -> Result<HeaderMap, AuthError> {
+    let mut value = HeaderValue::from_str(&format!("Bearer {token}"))
+        .map_err(|_| AuthError::InvalidCredential("bearer token is not a valid header value".to_string()))?;
+    value.set_sensitive(true);
+
+    let mut headers = HeaderMap::new();
+    headers.insert(AUTHORIZATION, value);
+    Ok(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingSource {
+        calls: AtomicUsize,
+        expires_in: Option<Duration>,
+    }
+
+    #[async_trait]
+    impl TokenSource for CountingSource {
+        async fn fetch(&self) -> Result<AccessToken, AuthError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            let token = AccessToken::new(format!("token-{call}"));
+            Ok(match self.expires_in {
+                Some(expires_in) => token.expires_in(expires_in),
+                None => token,
+            })
+        }
+    }
+
+    #[test]
+    fn test_access_token_parse() {
+        assert_eq!(AccessToken::parse(" abc \n").unwrap(), AccessToken::new("abc"));
+        assert_eq!(
+            AccessToken::parse(r#"{"access_token":"abc","expires_in":60}"#).unwrap(),
+            AccessToken::new("abc").expires_in(Duration::from_secs(60))
+        );
+        assert!(AccessToken::parse("  ").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_refreshing_token_is_cached_until_expiry_or_invalidation() {
+        let auth = RefreshingTokenAuth::new(CountingSource {
+            calls: AtomicUsize::new(0),
+            expires_in: Some(Duration::from_secs(3600)),
+        });
+
+        assert_eq!(auth.headers().await.unwrap()[AUTHORIZATION], "Bearer token-1");
+        assert_eq!(auth.headers().await.unwrap()[AUTHORIZATION], "Bearer token-1");
+
+        auth.invalidate().await;
+        assert_eq!(auth.headers().await.unwrap()[AUTHORIZATION], "Bearer token-2");
+
+        let expiring = RefreshingTokenAuth::new(CountingSource {
+            calls: AtomicUsize::new(0),
+            expires_in: Some(Duration::from_secs(10)),
+        });
+        assert_eq!(expiring.headers().await.unwrap()[AUTHORIZATION], "Bearer token-1");
+        assert_eq!(expiring.headers().await.unwrap()[AUTHORIZATION], "Bearer token-2");
+    }
+}
@@ -0,0 +1,96 @@
+//! Runs the Open Responses compliance suite against a server.
+//!
+//! ```text
+//! openresponses-compliance --base-url http://localhost:1234 --model my-model
+//! ```
+//!
+//! `--base-url` and `--api-key` default to the `API_URL` and `API_KEY`
+//! environment variables. Exits with status 1 when a scenario fails.
+
+use openresponses_rust::ResponsesClient;
+use openresponses_rust::compliance::{ComplianceSuite, Scenario};
+use std::env;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: openresponses-compliance --model <MODEL> [OPTIONS]
+
+Options:
+  --model <MODEL>          Model to send requests to
+  --base-url <URL>         Server base URL [env: API_URL] [default: https://api.openai.com]
+  --api-key <KEY>          API key [env: API_KEY]
+  --scenario <NAME>        Run only this scenario; repeat for several
+  --list                   List the available scenarios
+  -h, --help               Print this help";
+
+struct Args {
+    model: String,
+    base_url: String,
+    api_key: String,
+    scenarios: Vec<Scenario>,
+}
+
+fn parse_args() -> Result<Option<Args>, String> {
+    let mut model = None;
+    let mut base_url = env::var("API_URL").ok();
+    let mut api_key = env::var("API_KEY").ok();
+    let mut scenarios = Vec::new();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("`{arg}` needs a value"));
+        match arg.as_str() {
+            "--model" => model = Some(value()?),
+            "--base-url" => base_url = Some(value()?),
+            "--api-key" => api_key = Some(value()?),
+            "--scenario" => scenarios.push(value()?.parse()?),
+            "--list" => {
+                for scenario in Scenario::ALL {
+                    println!("{scenario}");
+                }
+                return Ok(None);
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(None);
+            }
+            other => return Err(format!("unexpected argument `{other}`")),
+        }
+    }
+
+    Ok(Some(Args {
+        model: model.ok_or("`--model` is required")?,
+        base_url: base_url.unwrap_or_else(|| "https://api.openai.com".to_string()),
+        api_key: api_key.unwrap_or_default(),
+        scenarios: if scenarios.is_empty() {
+            Scenario::ALL.to_vec()
+        } else {
+            scenarios
+        },
+    }))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(Some(args)) => args,
+        Ok(None) => return ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let client = ResponsesClient::with_base_url(args.api_key, args.base_url);
+    let report = ComplianceSuite::new(client, args.model)
+        .scenarios(args.scenarios)
+        .run()
+        .await;
+
+    println!("{report}");
+    if report.is_compliant() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
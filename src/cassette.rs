@@ -0,0 +1,322 @@
+//! Recording and offline replay of SSE streams.
+//!
+//! A cassette is either JSON Lines (`.jsonl`, one event per line) or a plain
+//! SSE transcript (`.sse`) where timing is kept in `: elapsed_ms=N` comments,
+//! so the file stays a valid event stream.
+
+use futures::{Stream, StreamExt, stream};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+use crate::streaming::{RawSseEvent, ResponseStream, StreamingError};
+use crate::validation::{EventParser, ValidationMode, ValidationPolicy};
+
+const ELAPSED_PREFIX: &str = "elapsed_ms=";
+
+#[derive(Error, Debug)]
+pub enum CassetteError {
+    #[error("Cassette I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid cassette line {line}: {source}")]
+    Json {
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("Unsupported cassette format `{}`, expected .jsonl or .sse", path.display())]
+    UnsupportedFormat { path: PathBuf },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CassetteEvent {
+    pub event: RawSseEvent,
+    /// Time since the start of the recording.
+    pub elapsed: Duration,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CassetteLine {
+    elapsed_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event: Option<String>,
+    data: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cassette {
+    events: Vec<CassetteEvent>,
+}
+
+impl Cassette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tees every event of `stream` into a cassette while passing it through.
+    pub fn record<S>(stream: S) -> Recorder<S> {
+        Recorder {
+            inner: stream,
+            cassette: Cassette::new(),
+            started: Instant::now(),
+        }
+    }
+
+    pub fn push(&mut self, event: RawSseEvent, elapsed: Duration) {
+        self.events.push(CassetteEvent { event, elapsed });
+    }
+
+    pub fn events(&self) -> &[CassetteEvent] {
+        &self.events
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Loads a `.jsonl` or `.sse` cassette, chosen by file extension.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CassetteError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        match Format::of(path)? {
+            Format::Jsonl => Self::from_jsonl(&contents),
+            Format::Sse => Ok(Self::from_sse(&contents)),
+        }
+    }
+
+    /// Saves as `.jsonl` or `.sse`, chosen by file extension.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CassetteError> {
+        let path = path.as_ref();
+        let contents = match Format::of(path)? {
+            Format::Jsonl => self.to_jsonl(),
+            Format::Sse => self.to_sse(),
+        };
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn from_jsonl(contents: &str) -> Result<Self, CassetteError> {
+        let mut cassette = Cassette::new();
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line: CassetteLine = serde_json::from_str(line).map_err(|source| CassetteError::Json {
+                line: index + 1,
+                source,
+            })?;
+            cassette.push(
+                RawSseEvent {
+                    event: line.event,
+                    data: line.data,
+                },
+                Duration::from_millis(line.elapsed_ms),
+            );
+        }
+        Ok(cassette)
+    }
+
+    pub fn to_jsonl(&self) -> String {
+        let mut contents = String::new();
+        for recorded in &self.events {
+            let line = CassetteLine {
+                elapsed_ms: recorded.elapsed.as_millis() as u64,
+                event: recorded.event.event.clone(),
+                data: recorded.event.data.clone(),
+            };
+            contents.push_str(&serde_json::to_string(&line).expect("cassette line serializes"));
+            contents.push('\n');
+        }
+        contents
+    }
+
+    /// Parses an SSE transcript. Frames without an `elapsed_ms` comment inherit
+    /// the previous frame's timing, so hand-written `.sse` files work too.
+    pub fn from_sse(contents: &str) -> Self {
+        let mut cassette = Cassette::new();
+        let mut elapsed = Duration::ZERO;
+        let mut event = None;
+        let mut data: Option<String> = None;
+
+        for line in contents.lines().chain(std::iter::once("")) {
+            if line.is_empty() {
+                if let Some(data) = data.take() {
+                    cassette.push(
+                        RawSseEvent {
+                            event: event.take(),
+                            data,
+                        },
+                        elapsed,
+                    );
+                }
+                event = None;
+                continue;
+            }
+
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line, ""),
+            };
+            match field {
+                "" => {
+                    if let Some(millis) = value.strip_prefix(ELAPSED_PREFIX) {
+                        if let Ok(millis) = millis.trim().parse() {
+                            elapsed = Duration::from_millis(millis);
+                        }
+                    }
+                }
+                "event" => event = Some(value.to_string()),
+                "data" => match &mut data {
+                    Some(data) => {
+                        data.push('\n');
+                        data.push_str(value);
+                    }
+                    None => data = Some(value.to_string()),
+                },
+                _ => {}
+            }
+        }
+
+        cassette
+    }
+
+    pub fn to_sse(&self) -> String {
+        let mut contents = String::new();
+        for recorded in &self.events {
+            let _ = writeln!(contents, ": {ELAPSED_PREFIX}{}", recorded.elapsed.as_millis());
+            if let Some(event) = &recorded.event.event {
+                let _ = writeln!(contents, "event: {event}");
+            }
+            for line in recorded.event.data.split('\n') {
+                let _ = writeln!(contents, "data: {line}");
+            }
+            contents.push('\n');
+        }
+        contents
+    }
+
+    /// Replays the cassette through the same parsing and validation as a live
+    /// stream, without delays.
+    pub fn replay(&self) -> ResponseStream {
+        self.replay_with(ReplayOptions::default())
+    }
+
+    pub fn replay_with(&self, options: ReplayOptions) -> ResponseStream {
+        let parser = EventParser::new(ValidationPolicy {
+            mode: options.validation_mode,
+            on_violation: None,
+        });
+        let report = parser.report();
+
+        let state = Replay {
+            events: self.events.clone().into_iter(),
+            parser,
+            realtime: options.realtime,
+            last_elapsed: Duration::ZERO,
+        };
+        let events = stream::unfold(state, |mut state| async move {
+            let recorded = state.events.next()?;
+            if state.realtime {
+                let delay = recorded.elapsed.saturating_sub(state.last_elapsed);
+                tokio::time::sleep(delay).await;
+            }
+            state.last_elapsed = recorded.elapsed;
+            let item = state.parser.parse_raw(&recorded.event);
+            Some((item, state))
+        });
+
+        ResponseStream::new(events, report)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayOptions {
+    pub realtime: bool,
+    pub validation_mode: ValidationMode,
+}
+
+impl ReplayOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reproduces the recorded delays between events.
+    pub fn realtime(mut self, realtime: bool) -> Self {
+        self.realtime = realtime;
+        self
+    }
+
+    pub fn validation_mode(mut self, mode: ValidationMode) -> Self {
+        self.validation_mode = mode;
+        self
+    }
+}
+
+struct Replay {
+    events: std::vec::IntoIter<CassetteEvent>,
+    parser: EventParser,
+    realtime: bool,
+    last_elapsed: Duration,
+}
+
+/// Stream returned by [`Cassette::record`].
+pub struct Recorder<S> {
+    inner: S,
+    cassette: Cassette,
+    started: Instant,
+}
+
+impl<S> Recorder<S> {
+    pub fn cassette(&self) -> &Cassette {
+        &self.cassette
+    }
+
+    pub fn into_cassette(self) -> Cassette {
+        self.cassette
+    }
+}
+
+impl<S> Stream for Recorder<S>
+where
+    S: Stream<Item = Result<RawSseEvent, StreamingError>> + Unpin,
+{
+    type Item = Result<RawSseEvent, StreamingError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let item = this.inner.poll_next_unpin(cx);
+        if let Poll::Ready(Some(Ok(event))) = &item {
+            // Cassettes store whole milliseconds; truncate so saving is lossless.
+            let elapsed = Duration::from_millis(this.started.elapsed().as_millis() as u64);
+            this.cassette.push(event.clone(), elapsed);
+        }
+        item
+    }
+}
+
+enum Format {
+    Jsonl,
+    Sse,
+}
+
+impl Format {
+    fn of(path: &Path) -> Result<Self, CassetteError> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("jsonl") => Ok(Format::Jsonl),
+            Some("sse") => Ok(Format::Sse),
+            _ => Err(CassetteError::UnsupportedFormat {
+                path: path.to_path_buf(),
+            }),
+        }
+    }
+}
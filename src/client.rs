@@ -1,10 +1,19 @@
-use reqwest::{Client as ReqwestClient, header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue}};
+use futures::Stream;
+use reqwest::Proxy;
 use serde_json;
+use std::time::Duration;
 use thiserror::Error;
 
-use crate::types::{ApiErrorResponse, CreateResponseBody, ResponseResource};
-
-const DEFAULT_BASE_URL: &str = "https://api.openai.com";
+use crate::auth::{AuthError, AuthProvider};
+use crate::metadata::ApiResponse;
+use crate::options::RequestOptions;
+use crate::polling::PollOptions;
+use crate::responses_client::{BuildError, ResponsesClient, ResponsesClientBuilder};
+use crate::retry::{FailedResponse, RetryPolicy};
+use crate::types::{
+    ApiErrorResponse, CreateResponseBody, DeletedResponse, InputItemList, Item,
+    ListInputItemsParams, ResponseResource, ResponseStatus,
+};
 
 #[derive(Error, Debug)]
 pub enum ClientError {
@@ -17,69 +26,101 @@ pub enum ClientError {
     #[error("API error ({status_code}): {raw_body}")]
     ApiError {
         status_code: u16,
-        error: Option<crate::types::ApiErrorDetail>,
+        error: Option<Box<crate::types::ApiErrorDetail>>,
         raw_body: String,
+        /// `x-request-id` of the failed request, when the server sent one.
+        request_id: Option<String>,
     },
     
     #[error("Invalid header value: {0}")]
     InvalidHeader(String),
+    
+    #[error("Authentication failed: {0}")]
+    Auth(#[from] AuthError),
+    
+    #[error("Timed out waiting for response `{response_id}` (last status: {last_status})")]
+    PollTimeout {
+        response_id: String,
+        last_status: ResponseStatus,
+    },
+    
+    #[error("Waiting for response `{response_id}` was cancelled")]
+    Cancelled { response_id: String },
 }
 
 pub struct ClientBuilder {
-    api_key: String,
-    base_url: Option<String>,
+    inner: ResponsesClientBuilder,
 }
 
 impl ClientBuilder {
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
-            api_key: api_key.into(),
-            base_url: None,
+            inner: ResponsesClientBuilder::new(api_key),
         }
     }
 
     pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
-        self.base_url = Some(base_url.into());
+        self.inner = self.inner.base_url(base_url);
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.inner = self.inner.retry_policy(retry_policy);
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.connect_timeout(timeout);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.timeout(timeout);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.inner = self.inner.proxy(proxy);
+        self
+    }
+
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.inner = self.inner.default_header(name, value);
+        self
+    }
+
+    pub fn organization(mut self, organization: impl Into<String>) -> Self {
+        self.inner = self.inner.organization(organization);
+        self
+    }
+
+    pub fn project(mut self, project: impl Into<String>) -> Self {
+        self.inner = self.inner.project(project);
+        self
+    }
+
+    pub fn auth(mut self, auth: impl AuthProvider + 'static) -> Self {
+        self.inner = self.inner.auth(auth);
         self
     }
 
     pub fn build(self) -> Client {
-        let mut base_url = self.base_url.unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
-        
-        // Remove trailing slash if present
-        if base_url.ends_with('/') {
-            base_url.pop();
-        }
-        
-        // Automatically append /v1 if it's not present in the path
-        if !base_url.ends_with("/v1") {
-            base_url.push_str("/v1");
-        }
-        
-        let mut headers = HeaderMap::new();
-        headers.insert(
-            CONTENT_TYPE,
-            HeaderValue::from_static("application/json"),
-        );
-        
-        let inner = ReqwestClient::builder()
-            .default_headers(headers)
-            .build()
-            .expect("Failed to create HTTP client");
-        
         Client {
-            inner,
-            base_url,
-            api_key: self.api_key,
+            inner: self.inner.build(),
         }
     }
+
+    pub fn try_build(self) -> Result<Client, BuildError> {
+        Ok(Client {
+            inner: self.inner.try_build()?,
+        })
+    }
 }
 
+/// Non-streaming view of a [`ResponsesClient`].
 #[derive(Clone)]
 pub struct Client {
-    inner: ReqwestClient,
-    base_url: String,
-    api_key: String,
+    pub(crate) inner: ResponsesClient,
 }
 
 impl Client {
@@ -96,52 +137,141 @@ impl Client {
     }
     
     pub async fn create_response(&self, request: CreateResponseBody) -> Result<ResponseResource, ClientError> {
-        let url = format!("{}/responses", self.base_url);
-        
-        let response = self.inner
-            .post(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.api_key))
-            .json(&request)
-            .send()
-            .await?;
-        
-        let status = response.status();
-        
-        if !status.is_success() {
-            let error_text = response.text().await?;
-            return Err(ClientError::ApiError {
-                status_code: status.as_u16(),
-                error: ApiErrorResponse::parse(&error_text),
-                raw_body: error_text,
-            });
-        }
-        
-        let response_body = response.json::<ResponseResource>().await?;
-        Ok(response_body)
+        self.inner.create_response(request).await
+    }
+    
+    pub async fn create_response_with_options(
+        &self,
+        request: CreateResponseBody,
+        options: RequestOptions,
+    ) -> Result<ResponseResource, ClientError> {
+        self.inner.create_response_with_options(request, options).await
+    }
+    
+    pub async fn create_response_with_metadata(
+        &self,
+        request: CreateResponseBody,
+        options: RequestOptions,
+    ) -> Result<ApiResponse<ResponseResource>, ClientError> {
+        self.inner.create_response_with_metadata(request, options).await
     }
     
     pub async fn create_response_raw(&self, request: CreateResponseBody) -> Result<String, ClientError> {
-        let url = format!("{}/responses", self.base_url);
-        
-        let response = self.inner
-            .post(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.api_key))
-            .json(&request)
-            .send()
-            .await?;
-        
-        let status = response.status();
-        let body = response.text().await?;
-        
-        if !status.is_success() {
-            return Err(ClientError::ApiError {
-                status_code: status.as_u16(),
-                error: ApiErrorResponse::parse(&body),
-                raw_body: body,
-            });
-        }
-        
-        Ok(body)
+        self.inner.create_response_raw(request).await
+    }
+    
+    pub async fn get_response(&self, response_id: &str) -> Result<ResponseResource, ClientError> {
+        self.inner.get_response(response_id).await
+    }
+    
+    pub async fn get_response_with_metadata(
+        &self,
+        response_id: &str,
+        options: RequestOptions,
+    ) -> Result<ApiResponse<ResponseResource>, ClientError> {
+        self.inner.get_response_with_metadata(response_id, options).await
+    }
+    
+    pub async fn get_response_with_options(
+        &self,
+        response_id: &str,
+        options: RequestOptions,
+    ) -> Result<ResponseResource, ClientError> {
+        self.inner.get_response_with_options(response_id, options).await
+    }
+    
+    pub async fn delete_response(&self, response_id: &str) -> Result<DeletedResponse, ClientError> {
+        self.inner.delete_response(response_id).await
+    }
+    
+    pub async fn delete_response_with_options(
+        &self,
+        response_id: &str,
+        options: RequestOptions,
+    ) -> Result<DeletedResponse, ClientError> {
+        self.inner.delete_response_with_options(response_id, options).await
+    }
+    
+    pub async fn cancel_response(&self, response_id: &str) -> Result<ResponseResource, ClientError> {
+        self.inner.cancel_response(response_id).await
+    }
+    
+    pub async fn cancel_response_with_options(
+        &self,
+        response_id: &str,
+        options: RequestOptions,
+    ) -> Result<ResponseResource, ClientError> {
+        self.inner.cancel_response_with_options(response_id, options).await
+    }
+    
+    pub async fn list_input_items(
+        &self,
+        response_id: &str,
+        params: ListInputItemsParams,
+    ) -> Result<InputItemList, ClientError> {
+        self.inner.list_input_items(response_id, params).await
+    }
+    
+    pub async fn list_input_items_with_options(
+        &self,
+        response_id: &str,
+        params: ListInputItemsParams,
+        options: RequestOptions,
+    ) -> Result<InputItemList, ClientError> {
+        self.inner
+            .list_input_items_with_options(response_id, params, options)
+            .await
+    }
+    
+    pub fn list_input_items_stream(
+        &self,
+        response_id: &str,
+        params: ListInputItemsParams,
+    ) -> impl Stream<Item = Result<Item, ClientError>> + Unpin + use<> {
+        self.inner.list_input_items_stream(response_id, params)
+    }
+    
+    pub fn list_input_items_stream_with_options(
+        &self,
+        response_id: &str,
+        params: ListInputItemsParams,
+        options: RequestOptions,
+    ) -> impl Stream<Item = Result<Item, ClientError>> + Unpin + use<> {
+        self.inner
+            .list_input_items_stream_with_options(response_id, params, options)
+    }
+    
+    pub async fn wait_for_completion(
+        &self,
+        response_id: &str,
+        options: PollOptions,
+    ) -> Result<ResponseResource, ClientError> {
+        self.inner.wait_for_completion(response_id, options).await
+    }
+    
+    pub fn responses_client(&self) -> &ResponsesClient {
+        &self.inner
+    }
+}
+
+impl From<ResponsesClient> for Client {
+    fn from(inner: ResponsesClient) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<Client> for ResponsesClient {
+    fn from(client: Client) -> Self {
+        client.inner
+    }
+}
+
+pub(crate) fn api_error(failed: FailedResponse) -> ClientError {
+    ClientError::ApiError {
+        status_code: failed.status,
+        error: ApiErrorResponse::parse(&failed.body).map(Box::new),
+        raw_body: failed.body,
+        request_id: failed.request_id,
     }
 }
 
@@ -153,23 +283,23 @@ mod tests {
     #[test]
     fn test_client_creation() {
         let client = Client::new("test-api-key");
-        assert_eq!(client.api_key, "test-api-key");
-        assert_eq!(client.base_url, "https://api.openai.com/v1");
+        assert_eq!(client.inner.api_key, "test-api-key");
+        assert_eq!(client.inner.base_url, "https://api.openai.com/v1");
     }
     
     #[test]
     fn test_client_with_base_url_normalization() {
         // Domain only
         let client = Client::with_base_url("test-key", "https://openrouter.ai/api");
-        assert_eq!(client.base_url, "https://openrouter.ai/api/v1");
+        assert_eq!(client.inner.base_url, "https://openrouter.ai/api/v1");
         
         // Already includes v1
         let client = Client::with_base_url("test-key", "https://openrouter.ai/api/v1");
-        assert_eq!(client.base_url, "https://openrouter.ai/api/v1");
+        assert_eq!(client.inner.base_url, "https://openrouter.ai/api/v1");
 
         // Localhost
         let client = Client::with_base_url("test-key", "http://localhost:1234");
-        assert_eq!(client.base_url, "http://localhost:1234/v1");
+        assert_eq!(client.inner.base_url, "http://localhost:1234/v1");
     }
     
     #[tokio::test]
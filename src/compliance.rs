@@ -0,0 +1,442 @@
+//! Checks which parts of the Open Responses spec a server implements.
+//!
+//! Requires the `compliance` feature. The `openresponses-compliance` binary
+//! runs the same suite from the command line.
+//!
+//! ```rust,no_run
+//! use openresponses_rust::ResponsesClient;
+//! use openresponses_rust::compliance::ComplianceSuite;
+//!
+//! # async fn run() {
+//! let client = ResponsesClient::with_base_url("test-key", "http://localhost:1234");
+//! let report = ComplianceSuite::new(client, "my-model").run().await;
+//!
+//! println!("{report}");
+//! assert!(report.is_compliant());
+//! # }
+//! ```
+
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::accumulator::StreamAccumulator;
+use crate::client::ClientError;
+use crate::responses_client::ResponsesClient;
+use crate::types::{
+    CreateResponseBody, FunctionOutput, Input, Item, MessageContent, ResponseResource,
+    ResponseStatus, StreamingEvent, TextFormat, TextParam, Tool, ToolChoice, ToolChoiceParam,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scenario {
+    /// A non-streaming text response.
+    PlainText,
+    /// A streamed response that passes strict validation and assembles into
+    /// the `response.completed` payload.
+    Streaming,
+    /// A forced function call followed by a `function_call_output` round trip.
+    FunctionCalling,
+    /// A follow-up request chained with `previous_response_id`.
+    PreviousResponseId,
+    /// A `json_schema` text format whose output parses against the schema.
+    StructuredOutput,
+    /// An invalid request answered with an `ApiErrorResponse` body.
+    ErrorShape,
+}
+
+impl Scenario {
+    pub const ALL: [Scenario; 6] = [
+        Scenario::PlainText,
+        Scenario::Streaming,
+        Scenario::FunctionCalling,
+        Scenario::PreviousResponseId,
+        Scenario::StructuredOutput,
+        Scenario::ErrorShape,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scenario::PlainText => "plain_text",
+            Scenario::Streaming => "streaming",
+            Scenario::FunctionCalling => "function_calling",
+            Scenario::PreviousResponseId => "previous_response_id",
+            Scenario::StructuredOutput => "structured_output",
+            Scenario::ErrorShape => "error_shape",
+        }
+    }
+}
+
+impl fmt::Display for Scenario {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Scenario {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Scenario::ALL
+            .into_iter()
+            .find(|scenario| scenario.name() == name)
+            .ok_or_else(|| format!("unknown scenario `{name}`"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ScenarioResult {
+    pub scenario: Scenario,
+    pub outcome: Outcome,
+    pub duration: Duration,
+}
+
+impl ScenarioResult {
+    pub fn passed(&self) -> bool {
+        self.outcome == Outcome::Passed
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ComplianceReport {
+    pub model: String,
+    pub results: Vec<ScenarioResult>,
+}
+
+impl ComplianceReport {
+    pub fn passed(&self) -> impl Iterator<Item = &ScenarioResult> {
+        self.results.iter().filter(|result| result.passed())
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &ScenarioResult> {
+        self.results.iter().filter(|result| !result.passed())
+    }
+
+    /// Whether every scenario that was run passed.
+    pub fn is_compliant(&self) -> bool {
+        self.failed().next().is_none()
+    }
+
+    pub fn result(&self, scenario: Scenario) -> Option<&ScenarioResult> {
+        self.results.iter().find(|result| result.scenario == scenario)
+    }
+}
+
+impl fmt::Display for ComplianceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Open Responses compliance for `{}`", self.model)?;
+        for result in &self.results {
+            let millis = result.duration.as_millis();
+            match &result.outcome {
+                Outcome::Passed => writeln!(f, "  PASS  {:<22} {millis} ms", result.scenario.name())?,
+                Outcome::Failed(reason) => writeln!(
+                    f,
+                    "  FAIL  {:<22} {millis} ms\n        {reason}",
+                    result.scenario.name()
+                )?,
+            }
+        }
+        write!(
+            f,
+            "{} of {} scenarios passed",
+            self.passed().count(),
+            self.results.len()
+        )
+    }
+}
+
+/// Runs [`Scenario`]s one after another against the server behind `client`.
+///
+/// Scenarios talk to a real model, so they only check the shape of what comes
+/// back (items, statuses, event order, parseable JSON), never its wording.
+pub struct ComplianceSuite {
+    client: ResponsesClient,
+    model: String,
+    scenarios: Vec<Scenario>,
+}
+
+type Check = Result<(), String>;
+
+impl ComplianceSuite {
+    pub fn new(client: ResponsesClient, model: impl Into<String>) -> Self {
+        Self {
+            client,
+            model: model.into(),
+            scenarios: Scenario::ALL.to_vec(),
+        }
+    }
+
+    /// Restricts the suite to `scenarios`, run in the given order.
+    pub fn scenarios(mut self, scenarios: Vec<Scenario>) -> Self {
+        self.scenarios = scenarios;
+        self
+    }
+
+    pub async fn run(&self) -> ComplianceReport {
+        let mut results = Vec::with_capacity(self.scenarios.len());
+        for scenario in &self.scenarios {
+            results.push(self.run_scenario(*scenario).await);
+        }
+        ComplianceReport {
+            model: self.model.clone(),
+            results,
+        }
+    }
+
+    pub async fn run_scenario(&self, scenario: Scenario) -> ScenarioResult {
+        let started = Instant::now();
+        let check = match scenario {
+            Scenario::PlainText => self.plain_text().await,
+            Scenario::Streaming => self.streaming().await,
+            Scenario::FunctionCalling => self.function_calling().await,
+            Scenario::PreviousResponseId => self.previous_response_id().await,
+            Scenario::StructuredOutput => self.structured_output().await,
+            Scenario::ErrorShape => self.error_shape().await,
+        };
+        ScenarioResult {
+            scenario,
+            outcome: match check {
+                Ok(()) => Outcome::Passed,
+                Err(reason) => Outcome::Failed(reason),
+            },
+            duration: started.elapsed(),
+        }
+    }
+
+    fn request(&self, input: Input) -> CreateResponseBody {
+        CreateResponseBody {
+            model: Some(self.model.clone()),
+            input: Some(input),
+            ..Default::default()
+        }
+    }
+
+    async fn create(&self, request: CreateResponseBody) -> Result<ResponseResource, String> {
+        let response = self
+            .client
+            .create_response(request)
+            .await
+            .map_err(|error| format!("request failed: {error}"))?;
+        check_completed(&response)?;
+        Ok(response)
+    }
+
+    async fn plain_text(&self) -> Check {
+        let response = self
+            .create(self.request(Input::Single("Say hello in one word.".to_string())))
+            .await?;
+        ensure(
+            response.object == "response",
+            format!("`object` is `{}`, expected `response`", response.object),
+        )?;
+        ensure(!response.id.is_empty(), "`id` is empty")?;
+        output_text(&response).map(drop)
+    }
+
+    async fn streaming(&self) -> Check {
+        let mut request = self.request(Input::Single("Count from one to five.".to_string()));
+        request.stream = Some(true);
+        let mut stream = self
+            .client
+            .stream_response(request)
+            .await
+            .map_err(|error| format!("request failed: {error}"))?;
+
+        let mut accumulator = StreamAccumulator::new();
+        let mut completed = None;
+        let mut first = None;
+        let mut done = false;
+        while let Some(event) = stream.next().await {
+            let event = event.map_err(|error| format!("invalid stream: {error}"))?;
+            first.get_or_insert_with(|| event.event_type().to_string());
+            done = matches!(event, StreamingEvent::Done);
+            if let Some(response) = accumulator
+                .apply(&event)
+                .map_err(|error| format!("events do not assemble: {error}"))?
+            {
+                completed = Some(response.clone());
+            }
+        }
+
+        ensure(
+            first.as_deref() == Some("response.created"),
+            format!("first event is {first:?}, expected `response.created`"),
+        )?;
+        ensure(done, "stream did not end with `[DONE]`")?;
+        let response = completed.ok_or("stream ended without a terminal response event")?;
+        check_completed(&response)?;
+        output_text(&response).map(drop)
+    }
+
+    async fn function_calling(&self) -> Check {
+        let tool = Tool::function("get_weather")
+            .with_description("Get the current weather for a city")
+            .with_parameters(json!({
+                "type": "object",
+                "properties": {"city": {"type": "string"}},
+                "required": ["city"],
+                "additionalProperties": false
+            }));
+        let question = Item::user_message("What is the weather in Seoul?");
+        let mut request = self.request(Input::Items(vec![question.clone()]));
+        request.tools = Some(vec![tool.clone()]);
+        request.tool_choice = Some(ToolChoiceParam::Simple(ToolChoice::Required));
+        let response = self.create(request).await?;
+
+        let call = response
+            .output
+            .iter()
+            .find(|item| matches!(item, Item::FunctionCall { .. }))
+            .ok_or("no `function_call` item although `tool_choice` was `required`")?;
+        let Item::FunctionCall {
+            call_id,
+            name,
+            arguments,
+            ..
+        } = call
+        else {
+            unreachable!();
+        };
+        ensure(name == "get_weather", format!("called unknown function `{name}`"))?;
+        let arguments: Value = serde_json::from_str(arguments)
+            .map_err(|error| format!("arguments are not JSON ({error}): {arguments}"))?;
+        ensure(
+            arguments.is_object(),
+            format!("arguments are not a JSON object: {arguments}"),
+        )?;
+
+        let output = Item::FunctionCallOutput {
+            id: None,
+            call_id: call_id.clone(),
+            output: FunctionOutput::Text(json!({"temperature_c": 21}).to_string()),
+            status: None,
+        };
+        let mut follow_up = self.request(Input::Items(vec![question, call.clone(), output]));
+        follow_up.tools = Some(vec![tool]);
+        let response = self.create(follow_up).await?;
+        output_text(&response).map(drop)
+    }
+
+    async fn previous_response_id(&self) -> Check {
+        let mut request = self.request(Input::Single("Remember the number 42.".to_string()));
+        request.store = Some(true);
+        let first = self.create(request).await?;
+
+        let mut follow_up = self.request(Input::Single(
+            "Which number did I ask you to remember?".to_string(),
+        ));
+        follow_up.previous_response_id = Some(first.id.clone());
+        let second = self.create(follow_up).await?;
+
+        ensure(
+            second.previous_response_id.as_deref() == Some(first.id.as_str()),
+            format!(
+                "`previous_response_id` is {:?}, expected {:?}",
+                second.previous_response_id, first.id
+            ),
+        )?;
+        output_text(&second).map(drop)
+    }
+
+    async fn structured_output(&self) -> Check {
+        let mut request = self.request(Input::Single(
+            "Give the name and country of the capital of Korea.".to_string(),
+        ));
+        request.text = Some(TextParam {
+            format: Some(TextFormat::JsonSchema {
+                name: "city".to_string(),
+                description: None,
+                schema: Some(json!({
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "country": {"type": "string"}
+                    },
+                    "required": ["name", "country"],
+                    "additionalProperties": false
+                })),
+                strict: Some(true),
+            }),
+            ..Default::default()
+        });
+        let response = self.create(request).await?;
+
+        let text = output_text(&response)?;
+        let value: Value = serde_json::from_str(&text)
+            .map_err(|error| format!("output is not JSON ({error}): {text}"))?;
+        for field in ["name", "country"] {
+            ensure(
+                value.get(field).is_some_and(Value::is_string),
+                format!("output is missing string field `{field}`: {value}"),
+            )?;
+        }
+        Ok(())
+    }
+
+    async fn error_shape(&self) -> Check {
+        let mut request = self.request(Input::Single("Hello".to_string()));
+        request.temperature = Some(100.0);
+
+        match self.client.create_response(request).await {
+            Ok(_) => Err("a `temperature` of 100 was accepted".to_string()),
+            Err(ClientError::ApiError {
+                status_code,
+                error,
+                raw_body,
+                ..
+            }) => {
+                ensure(
+                    (400..500).contains(&status_code),
+                    format!("status is {status_code}, expected 4xx"),
+                )?;
+                let detail =
+                    error.ok_or_else(|| format!("body is not an error object: {raw_body}"))?;
+                ensure(!detail.message.is_empty(), "`error.message` is empty")?;
+                ensure(detail.error_type.is_some(), "`error.type` is missing")
+            }
+            Err(error) => Err(format!("request failed: {error}")),
+        }
+    }
+}
+
+fn ensure(condition: bool, reason: impl Into<String>) -> Check {
+    if condition {
+        Ok(())
+    } else {
+        Err(reason.into())
+    }
+}
+
+fn check_completed(response: &ResponseResource) -> Check {
+    ensure(
+        response.status == ResponseStatus::Completed,
+        format!("status is `{:?}`, expected `completed`", response.status),
+    )
+}
+
+/// Text of all `output_text` parts, failing when there is none.
+fn output_text(response: &ResponseResource) -> Result<String, String> {
+    let text: String = response
+        .output
+        .iter()
+        .filter_map(|item| match item {
+            Item::Message { content, .. } => Some(content),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|part| match part {
+            MessageContent::OutputText { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    ensure(!text.trim().is_empty(), "no `output_text` in the response output")?;
+    Ok(text)
+}
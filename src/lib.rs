@@ -29,13 +29,55 @@
 //! }
 //! ```
 
+pub mod accumulator;
+pub mod auth;
+pub mod cassette;
 pub mod client;
+#[cfg(feature = "compliance")]
+pub mod compliance;
+pub mod metadata;
+pub mod options;
+pub mod polling;
+pub mod responses_client;
+pub mod retry;
+pub mod runner;
+pub mod schema;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod sse;
+pub mod stream_ext;
 pub mod streaming;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod text_stream;
+pub mod tool_registry;
 pub mod types;
+pub mod validation;
 
+pub use accumulator::{AccumulatorError, StreamAccumulator};
+pub use auth::{
+    AccessToken, ApiKeyAuth, AuthError, AuthProvider, BearerAuth, CommandTokenSource,
+    FileTokenSource, NoAuth, RefreshingTokenAuth, TokenSource,
+};
+pub use cassette::{Cassette, CassetteError, CassetteEvent, Recorder, ReplayOptions};
 pub use client::{Client, ClientError};
-pub use streaming::{RawSseEvent, StreamingClient, StreamingError};
+pub use metadata::{ApiResponse, RateLimitInfo, ResponseMetadata};
+pub use options::RequestOptions;
+pub use polling::PollOptions;
+pub use responses_client::{BuildError, ResponsesClient, ResponsesClientBuilder};
+pub use retry::RetryPolicy;
+pub use runner::{ConversationMode, RunEvent, RunResult, Runner, RunnerError};
+pub use schema::strict_schema;
+pub use sse::{EncodedStream, SseEncoder, SseParser};
+pub use stream_ext::{AccumulatedEvent, Accumulate, ResponseStreamExt};
+pub use streaming::{
+    RawSseEvent, ResponseStream, ResumeOptions, StreamingClient, StreamingError,
+};
+pub use text_stream::{TextChunk, TextChunks, TextStreamOptions};
+pub use tokio_util::sync::CancellationToken;
+pub use tool_registry::{ExecuteOptions, ToolError, ToolRegistry};
 pub use types::*;
+pub use validation::{ProtocolViolation, ValidationMode, ViolationReport};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,119 @@
+use reqwest::header::HeaderMap;
+use reqwest::Response;
+use std::time::Duration;
+
+use crate::retry::parse_reset_duration;
+
+const REQUEST_ID_HEADER: &str = "x-request-id";
+const PROCESSING_MS_HEADER: &str = "openai-processing-ms";
+
+/// A deserialized body together with the HTTP metadata it arrived with.
+#[derive(Debug, Clone)]
+pub struct ApiResponse<T> {
+    pub data: T,
+    pub metadata: ResponseMetadata,
+}
+
+impl<T> ApiResponse<T> {
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResponseMetadata {
+    pub status: u16,
+    /// `x-request-id`, which providers ask for in support tickets.
+    pub request_id: Option<String>,
+    /// Server-side processing time from `openai-processing-ms`.
+    pub processing_time: Option<Duration>,
+    pub rate_limit: RateLimitInfo,
+    pub headers: HeaderMap,
+}
+
+impl ResponseMetadata {
+    pub fn from_headers(status: u16, headers: &HeaderMap) -> Self {
+        Self {
+            status,
+            request_id: request_id(headers),
+            processing_time: header_str(headers, PROCESSING_MS_HEADER)
+                .and_then(|value| value.trim().parse::<f64>().ok())
+                .filter(|millis| millis.is_finite() && *millis >= 0.0)
+                .map(|millis| Duration::from_secs_f64(millis / 1000.0)),
+            rate_limit: RateLimitInfo::from_headers(headers),
+            headers: headers.clone(),
+        }
+    }
+
+    pub(crate) fn from_response(response: &Response) -> Self {
+        Self::from_headers(response.status().as_u16(), response.headers())
+    }
+}
+
+/// Parsed `x-ratelimit-*` headers. Fields are `None` when the provider does not send them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    pub limit_requests: Option<u64>,
+    pub remaining_requests: Option<u64>,
+    pub reset_requests: Option<Duration>,
+    pub limit_tokens: Option<u64>,
+    pub remaining_tokens: Option<u64>,
+    pub reset_tokens: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let count = |name: &str| header_str(headers, name).and_then(|value| value.trim().parse().ok());
+        let reset = |name: &str| header_str(headers, name).and_then(parse_reset_duration);
+
+        Self {
+            limit_requests: count("x-ratelimit-limit-requests"),
+            remaining_requests: count("x-ratelimit-remaining-requests"),
+            reset_requests: reset("x-ratelimit-reset-requests"),
+            limit_tokens: count("x-ratelimit-limit-tokens"),
+            remaining_tokens: count("x-ratelimit-remaining-tokens"),
+            reset_tokens: reset("x-ratelimit-reset-tokens"),
+        }
+    }
+}
+
+pub(crate) fn request_id(headers: &HeaderMap) -> Option<String> {
+    header_str(headers, REQUEST_ID_HEADER).map(str::to_string)
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_metadata_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", HeaderValue::from_static("req_123"));
+        headers.insert("openai-processing-ms", HeaderValue::from_static("250"));
+        headers.insert("x-ratelimit-limit-requests", HeaderValue::from_static("500"));
+        headers.insert("x-ratelimit-remaining-requests", HeaderValue::from_static("499"));
+        headers.insert("x-ratelimit-reset-requests", HeaderValue::from_static("120ms"));
+        headers.insert("x-ratelimit-remaining-tokens", HeaderValue::from_static("not-a-number"));
+        headers.insert("x-ratelimit-reset-tokens", HeaderValue::from_static("6m0s"));
+
+        let metadata = ResponseMetadata::from_headers(200, &headers);
+        assert_eq!(metadata.request_id.as_deref(), Some("req_123"));
+        assert_eq!(metadata.processing_time, Some(Duration::from_millis(250)));
+        assert_eq!(
+            metadata.rate_limit,
+            RateLimitInfo {
+                limit_requests: Some(500),
+                remaining_requests: Some(499),
+                reset_requests: Some(Duration::from_millis(120)),
+                limit_tokens: None,
+                remaining_tokens: None,
+                reset_tokens: Some(Duration::from_secs(360)),
+            }
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::time::Duration;
+
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Per-call overrides for a single request.
+///
+/// Values left as `None` fall back to the client configuration.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestOptions {
+    pub timeout: Option<Duration>,
+    pub idle_timeout: Option<Duration>,
+    pub headers: Vec<(String, String)>,
+    pub query: Vec<(String, String)>,
+    pub idempotency_key: Option<String>,
+}
+
+impl RequestOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Total time allowed for the request. For streaming calls this bounds the
+    /// whole stream, including the response body.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Maximum time to wait between two streaming events.
+    pub fn idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Adds a header to this request, replacing a client default with the same name.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((key.into(), value.into()));
+        self
+    }
+
+    /// Sent as `Idempotency-Key`. The same key is reused when the request is retried.
+    pub fn idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    pub(crate) fn header_map(&self) -> Result<HeaderMap, String> {
+        let mut headers = header_map(&self.headers)?;
+        if let Some(key) = &self.idempotency_key {
+            let value = HeaderValue::from_str(key)
+                .map_err(|_| format!("invalid value for header `{IDEMPOTENCY_KEY_HEADER}`"))?;
+            headers.insert(IDEMPOTENCY_KEY_HEADER, value);
+        }
+        Ok(headers)
+    }
+}
+
+pub(crate) fn header_map(pairs: &[(String, String)]) -> Result<HeaderMap, String> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in pairs {
+        let header_name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| format!("invalid header name `{name}`"))?;
+        let header_value = HeaderValue::from_str(value)
+            .map_err(|_| format!("invalid value for header `{name}`"))?;
+        headers.insert(header_name, header_value);
+    }
+    Ok(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_map_includes_idempotency_key() {
+        let headers = RequestOptions::new()
+            .header("X-Title", "my-app")
+            .idempotency_key("req-1")
+            .header_map()
+            .unwrap();
+
+        assert_eq!(headers["x-title"], "my-app");
+        assert_eq!(headers["idempotency-key"], "req-1");
+    }
+
+    #[test]
+    fn test_header_map_rejects_invalid_names_and_values() {
+        let error = RequestOptions::new().header("Bad Name", "x").header_map().unwrap_err();
+        assert!(error.contains("Bad Name"));
+
+        let error = RequestOptions::new().header("X-Title", "line\nbreak").header_map().unwrap_err();
+        assert!(error.contains("X-Title"));
+    }
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_INTERVAL: Duration = Duration::from_secs(10);
+const DEFAULT_BACKOFF_FACTOR: f64 = 1.5;
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Controls how `Client::wait_for_completion` polls a background response.
+///
+/// Polling gives up after `timeout`, 30 minutes by default. Set it to `None`
+/// to wait for as long as the response stays queued or in progress.
+#[derive(Debug, Clone)]
+pub struct PollOptions {
+    pub interval: Duration,
+    pub max_interval: Duration,
+    pub backoff_factor: f64,
+    pub timeout: Option<Duration>,
+    pub cancel_token: Option<CancellationToken>,
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_INTERVAL,
+            max_interval: DEFAULT_MAX_INTERVAL,
+            backoff_factor: DEFAULT_BACKOFF_FACTOR,
+            timeout: Some(DEFAULT_TIMEOUT),
+            cancel_token: None,
+        }
+    }
+}
+
+impl PollOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = max_interval;
+        self
+    }
+
+    pub fn backoff_factor(mut self, factor: f64) -> Self {
+        self.backoff_factor = factor;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Polls until the response settles, however long that takes.
+    pub fn no_timeout(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+
+    pub fn cancel_token(mut self, token: CancellationToken) -> Self {
+        self.cancel_token = Some(token);
+        self
+    }
+
+    pub(crate) fn next_interval(&self, current: Duration) -> Duration {
+        let factor = if self.backoff_factor.is_finite() && self.backoff_factor >= 1.0 {
+            self.backoff_factor
+        } else {
+            1.0
+        };
+
+        current.mul_f64(factor).min(self.max_interval.max(self.interval))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_is_capped_at_max_interval() {
+        let options = PollOptions::new()
+            .interval(Duration::from_millis(100))
+            .backoff_factor(2.0)
+            .max_interval(Duration::from_millis(300));
+
+        let second = options.next_interval(options.interval);
+        let third = options.next_interval(second);
+        let fourth = options.next_interval(third);
+
+        assert_eq!(second, Duration::from_millis(200));
+        assert_eq!(third, Duration::from_millis(300));
+        assert_eq!(fourth, Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_invalid_backoff_factor_keeps_interval_constant() {
+        let options = PollOptions::new()
+            .interval(Duration::from_millis(100))
+            .backoff_factor(0.5);
+
+        assert_eq!(options.next_interval(options.interval), Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,896 @@
+use eventsource_stream::Eventsource;
+use futures::{Stream, StreamExt, future, stream};
+use reqwest::{
+    header::{ACCEPT, CONTENT_TYPE, HeaderMap, HeaderValue},
+    Client as ReqwestClient, Proxy, RequestBuilder, Response,
+};
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+use crate::auth::{self, AuthError, AuthProvider};
+use crate::client::{self, ClientError};
+use crate::metadata::{ApiResponse, ResponseMetadata};
+use crate::options::{self, RequestOptions};
+use crate::polling::PollOptions;
+use crate::retry::{Retrier, RetryPolicy, SendOutcome};
+use crate::streaming::{
+    self, RawSseEvent, ResponseStream, ResumeOptions, SseEventStream, StreamingError,
+    with_idle_timeout,
+};
+use crate::types::{
+    CreateResponseBody, DeletedResponse, InputItemList, Item, ListInputItemsParams,
+    ResponseResource, ResponseStatus, StreamingEvent,
+};
+use crate::validation::{EventParser, ProtocolViolation, ValidationMode, ValidationPolicy};
+
+const DEFAULT_BASE_URL: &str = "https://api.openai.com";
+const ORGANIZATION_HEADER: &str = "OpenAI-Organization";
+const PROJECT_HEADER: &str = "OpenAI-Project";
+
+/// Invalid client configuration, reported by the builders' `try_build`.
+#[derive(Error, Debug)]
+pub enum BuildError {
+    #[error("Invalid header value: {0}")]
+    InvalidHeader(String),
+
+    #[error("Failed to create HTTP client: {0}")]
+    HttpClient(#[from] reqwest::Error),
+}
+
+pub struct ResponsesClientBuilder {
+    api_key: String,
+    base_url: Option<String>,
+    retry_policy: RetryPolicy,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    idle_timeout: Option<Duration>,
+    proxies: Vec<Proxy>,
+    default_headers: Vec<(String, String)>,
+    auth: Option<Arc<dyn AuthProvider>>,
+    validation: ValidationPolicy,
+}
+
+impl ResponsesClientBuilder {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            base_url: None,
+            retry_policy: RetryPolicy::none(),
+            connect_timeout: None,
+            timeout: None,
+            idle_timeout: None,
+            proxies: Vec::new(),
+            default_headers: Vec::new(),
+            auth: None,
+            validation: ValidationPolicy::default(),
+        }
+    }
+
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Retries apply to establishing a stream only: once the first event has
+    /// been delivered, failures are reported to the caller as they happen.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Total time allowed for a non-streaming request, including reading the body.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fails a stream with `StreamingError::IdleTimeout` when no event arrives
+    /// within `timeout`. Resumable streams reconnect instead when they can.
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Sends `name: value` with every request. Invalid names or values are
+    /// reported by [`try_build`](Self::try_build).
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn organization(self, organization: impl Into<String>) -> Self {
+        self.default_header(ORGANIZATION_HEADER, organization)
+    }
+
+    pub fn project(self, project: impl Into<String>) -> Self {
+        self.default_header(PROJECT_HEADER, project)
+    }
+
+    /// Replaces the default `Authorization: Bearer <api_key>` header.
+    pub fn auth(mut self, auth: impl AuthProvider + 'static) -> Self {
+        self.auth = Some(Arc::new(auth));
+        self
+    }
+
+    /// Defaults to [`ValidationMode::Strict`]. Servers that are slightly off-spec
+    /// can be streamed with [`ValidationMode::Warn`] or [`ValidationMode::Off`].
+    pub fn validation_mode(mut self, mode: ValidationMode) -> Self {
+        self.validation.mode = mode;
+        self
+    }
+
+    /// Called for every violation recorded in [`ValidationMode::Warn`].
+    pub fn on_violation<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&ProtocolViolation) + Send + Sync + 'static,
+    {
+        self.validation.on_violation = Some(Arc::new(callback));
+        self
+    }
+
+    /// Like [`try_build`](Self::try_build), but panics on invalid configuration.
+    pub fn build(self) -> ResponsesClient {
+        self.try_build()
+            .unwrap_or_else(|error| panic!("Invalid client configuration: {error}"))
+    }
+
+    pub fn try_build(self) -> Result<ResponsesClient, BuildError> {
+        let mut base_url = self.base_url.unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+
+        // Remove trailing slash if present
+        if base_url.ends_with('/') {
+            base_url.pop();
+        }
+
+        // Automatically append /v1 if it's not present in the path
+        if !base_url.ends_with("/v1") {
+            base_url.push_str("/v1");
+        }
+
+        let mut headers =
+            options::header_map(&self.default_headers).map_err(BuildError::InvalidHeader)?;
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+
+        let mut builder = ReqwestClient::builder().default_headers(headers);
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        for proxy in self.proxies {
+            builder = builder.proxy(proxy);
+        }
+
+        let http = builder.build()?;
+
+        Ok(ResponsesClient {
+            http,
+            base_url,
+            api_key: self.api_key,
+            retry_policy: self.retry_policy,
+            timeout: self.timeout,
+            idle_timeout: self.idle_timeout,
+            auth: self.auth,
+            validation: self.validation,
+        })
+    }
+}
+
+/// A single client for both plain and streaming calls.
+///
+/// Cloning is cheap and every clone shares the same connection pool.
+/// [`Client`](crate::Client) and [`StreamingClient`](crate::StreamingClient)
+/// are thin wrappers around it.
+#[derive(Clone)]
+pub struct ResponsesClient {
+    pub(crate) http: ReqwestClient,
+    pub(crate) base_url: String,
+    pub(crate) api_key: String,
+    retry_policy: RetryPolicy,
+    timeout: Option<Duration>,
+    idle_timeout: Option<Duration>,
+    auth: Option<Arc<dyn AuthProvider>>,
+    validation: ValidationPolicy,
+}
+
+impl ResponsesClient {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        ResponsesClientBuilder::new(api_key).build()
+    }
+
+    pub fn builder(api_key: impl Into<String>) -> ResponsesClientBuilder {
+        ResponsesClientBuilder::new(api_key)
+    }
+
+    pub fn with_base_url(api_key: impl Into<String>, base_url: impl Into<String>) -> Self {
+        ResponsesClientBuilder::new(api_key).base_url(base_url).build()
+    }
+
+    pub async fn create_response(&self, request: CreateResponseBody) -> Result<ResponseResource, ClientError> {
+        self.create_response_with_options(request, RequestOptions::default()).await
+    }
+
+    pub async fn create_response_with_options(
+        &self,
+        request: CreateResponseBody,
+        options: RequestOptions,
+    ) -> Result<ResponseResource, ClientError> {
+        self.create_response_with_metadata(request, options)
+            .await
+            .map(ApiResponse::into_inner)
+    }
+
+    /// Like [`create_response_with_options`](Self::create_response_with_options),
+    /// but also returns the status, request id, processing time and
+    /// rate-limit headers.
+    pub async fn create_response_with_metadata(
+        &self,
+        request: CreateResponseBody,
+        options: RequestOptions,
+    ) -> Result<ApiResponse<ResponseResource>, ClientError> {
+        let url = self.endpoint("/responses", &options);
+
+        let response = self
+            .send(|| self.http.post(&url).json(&request), &options)
+            .await?;
+
+        let metadata = ResponseMetadata::from_response(&response);
+        let data = parse_json_response(response).await?;
+        Ok(ApiResponse { data, metadata })
+    }
+
+    pub async fn create_response_raw(&self, request: CreateResponseBody) -> Result<String, ClientError> {
+        let url = format!("{}/responses", self.base_url);
+
+        let response = self
+            .send(|| self.http.post(&url).json(&request), &RequestOptions::default())
+            .await?;
+
+        let body = response.text().await?;
+        Ok(body)
+    }
+
+    pub async fn get_response(&self, response_id: &str) -> Result<ResponseResource, ClientError> {
+        self.get_response_with_options(response_id, RequestOptions::default())
+            .await
+    }
+
+    pub async fn get_response_with_options(
+        &self,
+        response_id: &str,
+        options: RequestOptions,
+    ) -> Result<ResponseResource, ClientError> {
+        self.get_response_with_metadata(response_id, options)
+            .await
+            .map(ApiResponse::into_inner)
+    }
+
+    /// Like [`get_response_with_options`](Self::get_response_with_options),
+    /// but also returns the HTTP metadata of the call.
+    pub async fn get_response_with_metadata(
+        &self,
+        response_id: &str,
+        options: RequestOptions,
+    ) -> Result<ApiResponse<ResponseResource>, ClientError> {
+        let url = self.endpoint(&format!("/responses/{response_id}"), &options);
+
+        let response = self.send(|| self.http.get(&url), &options).await?;
+
+        let metadata = ResponseMetadata::from_response(&response);
+        let data = parse_json_response(response).await?;
+        Ok(ApiResponse { data, metadata })
+    }
+
+    pub async fn delete_response(&self, response_id: &str) -> Result<DeletedResponse, ClientError> {
+        self.delete_response_with_options(response_id, RequestOptions::default())
+            .await
+    }
+
+    pub async fn delete_response_with_options(
+        &self,
+        response_id: &str,
+        options: RequestOptions,
+    ) -> Result<DeletedResponse, ClientError> {
+        let url = self.endpoint(&format!("/responses/{response_id}"), &options);
+
+        let response = self.send(|| self.http.delete(&url), &options).await?;
+
+        parse_json_response(response).await
+    }
+
+    pub async fn cancel_response(&self, response_id: &str) -> Result<ResponseResource, ClientError> {
+        self.cancel_response_with_options(response_id, RequestOptions::default())
+            .await
+    }
+
+    pub async fn cancel_response_with_options(
+        &self,
+        response_id: &str,
+        options: RequestOptions,
+    ) -> Result<ResponseResource, ClientError> {
+        let url = self.endpoint(&format!("/responses/{response_id}/cancel"), &options);
+
+        let response = self.send(|| self.http.post(&url), &options).await?;
+
+        parse_json_response(response).await
+    }
+
+    pub async fn list_input_items(
+        &self,
+        response_id: &str,
+        params: ListInputItemsParams,
+    ) -> Result<InputItemList, ClientError> {
+        self.list_input_items_with_options(response_id, params, RequestOptions::default())
+            .await
+    }
+
+    pub async fn list_input_items_with_options(
+        &self,
+        response_id: &str,
+        params: ListInputItemsParams,
+        options: RequestOptions,
+    ) -> Result<InputItemList, ClientError> {
+        let mut paged = RequestOptions::new();
+        for (key, value) in params.to_query_pairs() {
+            paged = paged.query(key, value);
+        }
+        paged.query.extend(options.query.iter().cloned());
+        let url = self.endpoint(&format!("/responses/{response_id}/input_items"), &paged);
+
+        let response = self.send(|| self.http.get(&url), &options).await?;
+
+        parse_json_response(response).await
+    }
+
+    pub fn list_input_items_stream(
+        &self,
+        response_id: &str,
+        params: ListInputItemsParams,
+    ) -> impl Stream<Item = Result<Item, ClientError>> + Unpin + use<> {
+        self.list_input_items_stream_with_options(response_id, params, RequestOptions::default())
+    }
+
+    /// Like [`list_input_items_stream`](Self::list_input_items_stream), with
+    /// `options` applied to every page request.
+    pub fn list_input_items_stream_with_options(
+        &self,
+        response_id: &str,
+        params: ListInputItemsParams,
+        options: RequestOptions,
+    ) -> impl Stream<Item = Result<Item, ClientError>> + Unpin + use<> {
+        let pager = InputItemPager {
+            client: self.clone(),
+            response_id: response_id.to_string(),
+            params,
+            options,
+            buffer: VecDeque::new(),
+            exhausted: false,
+        };
+
+        Box::pin(stream::unfold(pager, |mut pager| async move {
+            let item = pager.next_item().await?;
+            Some((item, pager))
+        }))
+    }
+
+    /// Polls `response_id` until it reaches a terminal status or one this
+    /// client does not recognize, within the limits of `options`.
+    pub async fn wait_for_completion(
+        &self,
+        response_id: &str,
+        options: PollOptions,
+    ) -> Result<ResponseResource, ClientError> {
+        let started = Instant::now();
+        let mut interval = options.interval;
+
+        loop {
+            let response = match &options.cancel_token {
+                Some(token) => tokio::select! {
+                    _ = token.cancelled() => {
+                        return Err(ClientError::Cancelled { response_id: response_id.to_string() });
+                    }
+                    response = self.get_response(response_id) => response?,
+                },
+                None => self.get_response(response_id).await?,
+            };
+
+            let settled = match &response.status {
+                // A status this client doesn't know can't be waited out.
+                ResponseStatus::Other(_) => true,
+                status => status.is_terminal(),
+            };
+            if settled {
+                return Ok(response);
+            }
+
+            let mut delay = interval;
+            if let Some(timeout) = options.timeout {
+                let elapsed = started.elapsed();
+                if elapsed >= timeout {
+                    return Err(ClientError::PollTimeout {
+                        response_id: response_id.to_string(),
+                        last_status: response.status,
+                    });
+                }
+                delay = delay.min(timeout - elapsed);
+            }
+
+            match &options.cancel_token {
+                Some(token) => tokio::select! {
+                    _ = token.cancelled() => {
+                        return Err(ClientError::Cancelled { response_id: response_id.to_string() });
+                    }
+                    _ = tokio::time::sleep(delay) => {}
+                },
+                None => tokio::time::sleep(delay).await,
+            }
+
+            interval = options.next_interval(interval);
+        }
+    }
+
+    pub async fn stream_response(
+        &self,
+        request: CreateResponseBody,
+    ) -> Result<ResponseStream, StreamingError> {
+        self.stream_response_with_options(request, RequestOptions::default())
+            .await
+    }
+
+    pub async fn stream_response_with_options(
+        &self,
+        mut request: CreateResponseBody,
+        options: RequestOptions,
+    ) -> Result<ResponseStream, StreamingError> {
+        request.stream = Some(true);
+
+        let url = self.endpoint("/responses", &options);
+
+        let (eventsource, metadata) = self
+            .open_events(|| self.http.post(&url).json(&request), &options)
+            .await?;
+        let mut parser = EventParser::new(self.validation.clone());
+        let report = parser.report();
+
+        let event_stream = eventsource.map(move |event| match event {
+            Ok(event) => parser.parse(event),
+            Err(error) => Err(StreamingError::StreamError(error.to_string())),
+        });
+
+        Ok(ResponseStream::new(
+            with_idle_timeout(event_stream, self.idle_timeout_for(&options)),
+            report,
+        )
+        .with_metadata(metadata))
+    }
+
+    /// Streams a response and transparently reconnects with `starting_after` if
+    /// the connection drops before the response reaches a terminal event.
+    ///
+    /// Reconnection is only attempted for stored or background responses, since
+    /// the server cannot replay anything else.
+    pub async fn stream_response_resumable(
+        &self,
+        request: CreateResponseBody,
+        options: ResumeOptions,
+    ) -> Result<ResponseStream, StreamingError> {
+        self.stream_response_resumable_with_options(request, options, RequestOptions::default())
+            .await
+    }
+
+    /// Like [`stream_response_resumable`](Self::stream_response_resumable),
+    /// with `request_options` applied to the initial request and to every
+    /// reconnect.
+    pub async fn stream_response_resumable_with_options(
+        &self,
+        mut request: CreateResponseBody,
+        options: ResumeOptions,
+        request_options: RequestOptions,
+    ) -> Result<ResponseStream, StreamingError> {
+        request.stream = Some(true);
+        let resumable = request.store != Some(false) || request.background == Some(true);
+
+        let url = self.endpoint("/responses", &request_options);
+
+        let (eventsource, metadata) = self
+            .open_events(|| self.http.post(&url).json(&request), &request_options)
+            .await?;
+
+        let parser = EventParser::new(self.validation.clone());
+        let report = parser.report();
+        let state = ResumableStream {
+            client: self.clone(),
+            events: eventsource,
+            idle_timeout: self.idle_timeout_for(&request_options),
+            parser,
+            options,
+            request_options,
+            resumable,
+            response_id: None,
+            last_sequence_number: None,
+            reconnects: 0,
+            finished: false,
+        };
+
+        let events = stream::unfold(state, |mut state| async move {
+            let item = state.next_event().await?;
+            Some((item, state))
+        });
+
+        Ok(ResponseStream::new(events, report).with_metadata(metadata))
+    }
+
+    pub async fn stream_response_lines(
+        &self,
+        mut request: CreateResponseBody,
+    ) -> Result<impl Stream<Item = Result<RawSseEvent, StreamingError>> + use<>, StreamingError> {
+        request.stream = Some(true);
+
+        let url = format!("{}/responses", self.base_url);
+
+        let (eventsource, _) = self
+            .open_events(
+                || self.http.post(&url).json(&request),
+                &RequestOptions::default(),
+            )
+            .await?;
+
+        let line_stream = eventsource.map(|event| match event {
+            Ok(event) => {
+                let event_name = if event.data == "[DONE]" || event.event.is_empty() {
+                    None
+                } else {
+                    Some(event.event)
+                };
+
+                Ok(RawSseEvent {
+                    event: event_name,
+                    data: event.data,
+                })
+            }
+            Err(error) => Err(StreamingError::StreamError(error.to_string())),
+        });
+
+        Ok(with_idle_timeout(line_stream, self.idle_timeout))
+    }
+
+    fn endpoint(&self, path: &str, options: &RequestOptions) -> String {
+        let mut url = format!("{}{}", self.base_url, path);
+        if !options.query.is_empty() {
+            let query = url::form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&options.query)
+                .finish();
+            url.push('?');
+            url.push_str(&query);
+        }
+        url
+    }
+
+    /// Applies the configuration shared by every call to a request.
+    fn prepare(
+        &self,
+        request: RequestBuilder,
+        timeout: Option<Duration>,
+        headers: &HeaderMap,
+    ) -> RequestBuilder {
+        let request = request.headers(headers.clone());
+        match timeout {
+            Some(timeout) => request.timeout(timeout),
+            None => request,
+        }
+    }
+
+    async fn auth_headers(&self) -> Result<HeaderMap, AuthError> {
+        match &self.auth {
+            Some(auth) => auth.headers().await,
+            None => auth::bearer_headers(&self.api_key),
+        }
+    }
+
+    /// Credentials first, so per-request headers can override them.
+    async fn request_headers(&self, options: &RequestOptions) -> Result<HeaderMap, ClientError> {
+        let mut headers = self.auth_headers().await?;
+        headers.extend(options.header_map().map_err(ClientError::InvalidHeader)?);
+        Ok(headers)
+    }
+
+    /// Invalidates the credentials after a `401`. Returns `true` when the
+    /// request should be sent once more with fresh ones.
+    async fn rejected(&self, status: u16, reauthenticated: &mut bool) -> bool {
+        let (401, Some(auth)) = (status, &self.auth) else {
+            return false;
+        };
+        auth.invalidate().await;
+        !std::mem::replace(reauthenticated, true)
+    }
+
+    async fn send<F>(&self, build: F, options: &RequestOptions) -> Result<Response, ClientError>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        let timeout = options.timeout.or(self.timeout);
+        let build = &build;
+        let mut retrier = Retrier::new(&self.retry_policy);
+        let mut reauthenticated = false;
+
+        loop {
+            let outcome = retrier
+                .send(move || async move {
+                    let headers = self.request_headers(options).await?;
+                    Ok::<_, ClientError>(self.prepare(build(), timeout, &headers))
+                })
+                .await?;
+
+            match outcome {
+                SendOutcome::Success(response) => return Ok(response),
+                SendOutcome::Failed(failed) => {
+                    if self.rejected(failed.status, &mut reauthenticated).await {
+                        continue;
+                    }
+                    return Err(client::api_error(failed));
+                }
+            }
+        }
+    }
+
+    async fn reconnect(
+        &self,
+        response_id: &str,
+        starting_after: Option<i32>,
+        options: &RequestOptions,
+    ) -> Result<SseEventStream, StreamingError> {
+        let mut resume = options.clone().query("stream", "true");
+        if let Some(sequence_number) = starting_after {
+            resume = resume.query("starting_after", sequence_number.to_string());
+        }
+        let url = self.endpoint(&format!("/responses/{response_id}"), &resume);
+
+        let (events, _) = self.open_events(|| self.http.get(&url), options).await?;
+        Ok(events)
+    }
+
+    fn idle_timeout_for(&self, options: &RequestOptions) -> Option<Duration> {
+        options.idle_timeout.or(self.idle_timeout)
+    }
+
+    async fn open_events<F>(
+        &self,
+        build: F,
+        options: &RequestOptions,
+    ) -> Result<(SseEventStream, ResponseMetadata), StreamingError>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        let build = &build;
+        let mut retrier = Retrier::new(&self.retry_policy);
+        let idle_timeout = self.idle_timeout_for(options);
+        let mut reauthenticated = false;
+
+        loop {
+            let outcome = retrier
+                .send(move || async move {
+                    let mut headers = self.auth_headers().await?;
+                    headers.extend(options.header_map().map_err(StreamingError::InvalidHeader)?);
+                    let request = self
+                        .prepare(build(), options.timeout, &headers)
+                        .header(ACCEPT, "text/event-stream");
+                    Ok::<_, StreamingError>(request)
+                })
+                .await?;
+
+            let response = match outcome {
+                SendOutcome::Success(response) => response,
+                SendOutcome::Failed(failed) => {
+                    if self.rejected(failed.status, &mut reauthenticated).await {
+                        continue;
+                    }
+                    return Err(streaming::api_error(failed));
+                }
+            };
+
+            let metadata = ResponseMetadata::from_response(&response);
+            let mut events: SseEventStream = Box::pin(response.bytes_stream().eventsource());
+            if !retrier.has_remaining_attempts() {
+                return Ok((events, metadata));
+            }
+
+            let first = match idle_timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, events.next()).await {
+                    Ok(first) => first,
+                    Err(_) if retrier.retry_stream().await => continue,
+                    Err(_) => return Err(StreamingError::IdleTimeout { timeout }),
+                },
+                None => events.next().await,
+            };
+
+            match first {
+                Some(Ok(first)) => {
+                    let events = stream::once(future::ready(Ok(first))).chain(events);
+                    return Ok((Box::pin(events), metadata));
+                }
+                Some(Err(error)) => {
+                    if !retrier.retry_stream().await {
+                        let events = stream::once(future::ready(Err(error)));
+                        return Ok((Box::pin(events), metadata));
+                    }
+                }
+                None => {
+                    if !retrier.retry_stream().await {
+                        return Ok((Box::pin(stream::empty()), metadata));
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct InputItemPager {
+    client: ResponsesClient,
+    response_id: String,
+    params: ListInputItemsParams,
+    options: RequestOptions,
+    buffer: VecDeque<Item>,
+    exhausted: bool,
+}
+
+impl InputItemPager {
+    async fn next_item(&mut self) -> Option<Result<Item, ClientError>> {
+        loop {
+            if let Some(item) = self.buffer.pop_front() {
+                return Some(Ok(item));
+            }
+
+            if self.exhausted {
+                return None;
+            }
+
+            let page = match self
+                .client
+                .list_input_items_with_options(
+                    &self.response_id,
+                    self.params.clone(),
+                    self.options.clone(),
+                )
+                .await
+            {
+                Ok(page) => page,
+                Err(error) => {
+                    self.exhausted = true;
+                    return Some(Err(error));
+                }
+            };
+
+            self.exhausted = !page.has_more || page.last_id.is_none();
+            self.params.after = page.last_id;
+            self.buffer.extend(page.data);
+        }
+    }
+}
+
+struct ResumableStream {
+    client: ResponsesClient,
+    events: SseEventStream,
+    idle_timeout: Option<Duration>,
+    parser: EventParser,
+    options: ResumeOptions,
+    request_options: RequestOptions,
+    resumable: bool,
+    response_id: Option<String>,
+    last_sequence_number: Option<i32>,
+    reconnects: u32,
+    finished: bool,
+}
+
+impl ResumableStream {
+    async fn next_event(&mut self) -> Option<Result<StreamingEvent, StreamingError>> {
+        loop {
+            if self.finished {
+                return None;
+            }
+
+            let next = match self.idle_timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, self.events.next()).await {
+                    Ok(next) => Ok(next),
+                    Err(_) => Err(StreamingError::IdleTimeout { timeout }),
+                },
+                None => Ok(self.events.next().await),
+            };
+
+            let transport_error = match next {
+                Err(error) => Some(error),
+                Ok(Some(Ok(event))) => {
+                    let parsed = self.parser.parse(event);
+                    if let Ok(event) = &parsed {
+                        self.track(event);
+                    }
+                    return Some(parsed);
+                }
+                Ok(Some(Err(error))) => Some(StreamingError::StreamError(error.to_string())),
+                Ok(None) => None,
+            };
+
+            let response_id = match &self.response_id {
+                Some(response_id) if self.can_reconnect() => response_id.clone(),
+                _ => {
+                    self.finished = true;
+                    return transport_error.map(Err);
+                }
+            };
+
+            self.reconnects += 1;
+            tokio::time::sleep(self.options.reconnect_delay).await;
+
+            match self
+                .client
+                .reconnect(&response_id, self.last_sequence_number, &self.request_options)
+                .await
+            {
+                Ok(events) => self.events = events,
+                Err(error) => {
+                    self.finished = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+    }
+
+    fn track(&mut self, event: &StreamingEvent) {
+        if let Some(sequence_number) = event.sequence_number() {
+            self.last_sequence_number = Some(sequence_number);
+        }
+        if let Some(response) = event.response() {
+            self.response_id.get_or_insert_with(|| response.id.clone());
+        }
+        if event.is_terminal() {
+            self.finished = matches!(event, StreamingEvent::Done);
+            self.resumable = false;
+        }
+    }
+
+    fn can_reconnect(&self) -> bool {
+        self.resumable && self.reconnects < self.options.max_reconnects
+    }
+}
+
+async fn parse_json_response<T: DeserializeOwned>(response: Response) -> Result<T, ClientError> {
+    let body = response.json::<T>().await?;
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_responses_client_creation() {
+        let client = ResponsesClient::new("test-api-key");
+        assert_eq!(client.api_key, "test-api-key");
+        assert_eq!(client.base_url, "https://api.openai.com/v1");
+    }
+
+    #[test]
+    fn test_wrappers_share_configuration() {
+        let client = ResponsesClient::builder("test-key")
+            .base_url("http://localhost:1234/")
+            .build();
+
+        let plain = crate::Client::from(client.clone());
+        let streaming = crate::StreamingClient::from(client);
+        assert_eq!(plain.inner.base_url, "http://localhost:1234/v1");
+        assert_eq!(streaming.inner.base_url, "http://localhost:1234/v1");
+    }
+}
@@ -0,0 +1,355 @@
+use std::future::Future;
+use std::time::Duration;
+
+use reqwest::{RequestBuilder, Response, header::HeaderMap};
+
+use crate::types::{ApiErrorResponse, ErrorType};
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(500);
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(30);
+const DEFAULT_JITTER: f64 = 0.25;
+const DEFAULT_RETRY_STATUSES: [u16; 6] = [408, 429, 500, 502, 503, 504];
+
+/// Controls how failed requests are retried.
+///
+/// `max_attempts` counts the initial request, so `1` disables retries. Delays
+/// grow exponentially from `base_delay` up to `max_delay`, unless the server
+/// asks for a specific wait through `Retry-After`, `retry-after-ms` or the
+/// `x-ratelimit-reset-*` headers.
+///
+/// Connection failures are only retried when nothing reached the server.
+/// Timeouts and errors after the request was sent, and streams that break
+/// before their first event, may already have started a billed generation;
+/// retrying them can create duplicates, so they need
+/// [`retry_sent_requests`](Self::retry_sent_requests). Pair that with an
+/// `Idempotency-Key` on servers that honor one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub jitter: f64,
+    pub retry_statuses: Vec<u16>,
+    pub retry_error_types: Vec<ErrorType>,
+    pub retry_connection_errors: bool,
+    pub retry_sent_requests: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            base_delay: DEFAULT_BASE_DELAY,
+            max_delay: DEFAULT_MAX_DELAY,
+            jitter: DEFAULT_JITTER,
+            retry_statuses: DEFAULT_RETRY_STATUSES.to_vec(),
+            retry_error_types: vec![ErrorType::ServerError, ErrorType::TooManyRequests],
+            retry_connection_errors: true,
+            retry_sent_requests: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn retry_statuses(mut self, statuses: Vec<u16>) -> Self {
+        self.retry_statuses = statuses;
+        self
+    }
+
+    pub fn retry_error_types(mut self, error_types: Vec<ErrorType>) -> Self {
+        self.retry_error_types = error_types;
+        self
+    }
+
+    pub fn retry_connection_errors(mut self, retry: bool) -> Self {
+        self.retry_connection_errors = retry;
+        self
+    }
+
+    /// Also retries requests that failed after they may have reached the
+    /// server. Off by default because it can duplicate generations.
+    pub fn retry_sent_requests(mut self, retry: bool) -> Self {
+        self.retry_sent_requests = retry;
+        self
+    }
+
+    pub fn is_retryable_response(&self, status: u16, body: &str) -> bool {
+        if self.retry_statuses.contains(&status) {
+            return true;
+        }
+
+        ApiErrorResponse::parse(body)
+            .and_then(|error| error.error_type)
+            .is_some_and(|error_type| {
+                self.retry_error_types
+                    .iter()
+                    .any(|retryable| retryable.as_str() == error_type)
+            })
+    }
+
+    pub fn delay_for(&self, attempt: u32, headers: Option<&HeaderMap>) -> Duration {
+        if let Some(delay) = headers.and_then(server_requested_delay) {
+            return delay.min(self.max_delay);
+        }
+
+        let exponent = attempt.saturating_sub(1).min(31);
+        let backoff = self
+            .base_delay
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_delay);
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return backoff;
+        }
+
+        backoff.mul_f64(1.0 - jitter * fastrand::f64())
+    }
+}
+
+pub(crate) struct FailedResponse {
+    pub status: u16,
+    pub request_id: Option<String>,
+    pub body: String,
+}
+
+pub(crate) enum SendOutcome {
+    Success(Response),
+    Failed(FailedResponse),
+}
+
+pub(crate) struct Retrier<'a> {
+    policy: &'a RetryPolicy,
+    attempt: u32,
+}
+
+impl<'a> Retrier<'a> {
+    pub fn new(policy: &'a RetryPolicy) -> Self {
+        Self { policy, attempt: 1 }
+    }
+
+    pub fn has_remaining_attempts(&self) -> bool {
+        self.attempt < self.policy.max_attempts
+    }
+
+    /// Sends the request built by `build`, which runs again before every
+    /// attempt so each one picks up current credentials.
+    pub async fn send<F, Fut, E>(&mut self, build: F) -> Result<SendOutcome, E>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<RequestBuilder, E>>,
+        E: From<reqwest::Error>,
+    {
+        loop {
+            match build().await?.send().await {
+                Ok(response) if response.status().is_success() => {
+                    return Ok(SendOutcome::Success(response));
+                }
+                Ok(response) => {
+                    let status = response.status().as_u16();
+                    let headers = response.headers().clone();
+                    let request_id = crate::metadata::request_id(&headers);
+                    let body = response.text().await?;
+
+                    if self.has_remaining_attempts()
+                        && self.policy.is_retryable_response(status, &body)
+                    {
+                        self.wait(Some(&headers)).await;
+                        continue;
+                    }
+
+                    return Ok(SendOutcome::Failed(FailedResponse {
+                        status,
+                        request_id,
+                        body,
+                    }));
+                }
+                Err(error) => {
+                    // Only a failed connect guarantees the server never saw the request.
+                    let sent_error = error.is_timeout() || error.is_request();
+                    if self.has_remaining_attempts()
+                        && self.policy.retry_connection_errors
+                        && (error.is_connect() || (sent_error && self.policy.retry_sent_requests))
+                    {
+                        self.wait(None).await;
+                        continue;
+                    }
+
+                    return Err(error.into());
+                }
+            }
+        }
+    }
+
+    /// Waits before retrying a stream that broke before delivering any events.
+    /// Returns `false` when the policy does not allow another attempt.
+    pub async fn retry_stream(&mut self) -> bool {
+        if !self.has_remaining_attempts()
+            || !self.policy.retry_connection_errors
+            || !self.policy.retry_sent_requests
+        {
+            return false;
+        }
+
+        self.wait(None).await;
+        true
+    }
+
+    async fn wait(&mut self, headers: Option<&HeaderMap>) {
+        let delay = self.policy.delay_for(self.attempt, headers);
+        self.attempt += 1;
+        tokio::time::sleep(delay).await;
+    }
+}
+
+fn server_requested_delay(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+    if let Some(millis) = header("retry-after-ms").and_then(|value| value.trim().parse::<f64>().ok()) {
+        return duration_from_secs(millis / 1000.0);
+    }
+
+    if let Some(seconds) = header("retry-after").and_then(|value| value.trim().parse::<f64>().ok()) {
+        return duration_from_secs(seconds);
+    }
+
+    ["x-ratelimit-reset-requests", "x-ratelimit-reset-tokens"]
+        .into_iter()
+        .filter_map(|name| header(name).and_then(parse_reset_duration))
+        .max()
+}
+
+fn duration_from_secs(seconds: f64) -> Option<Duration> {
+    if seconds.is_finite() && seconds >= 0.0 {
+        Some(Duration::from_secs_f64(seconds))
+    } else {
+        None
+    }
+}
+
+/// Parses rate-limit reset values such as `1s`, `6m0s`, `250ms` or `1h2m3.5s`.
+/// Plain numbers are treated as seconds.
+pub(crate) fn parse_reset_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    if let Ok(seconds) = value.parse::<f64>() {
+        return duration_from_secs(seconds);
+    }
+
+    let mut total = 0.0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        if number_len == 0 {
+            return None;
+        }
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let multiplier = match &rest[..unit_len] {
+            "h" => 3600.0,
+            "m" => 60.0,
+            "s" => 1.0,
+            "ms" => 0.001,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+
+        total += number * multiplier;
+    }
+
+    duration_from_secs(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_parse_reset_duration() {
+        assert_eq!(parse_reset_duration("1s"), Some(Duration::from_secs(1)));
+        assert_eq!(parse_reset_duration("6m0s"), Some(Duration::from_secs(360)));
+        assert_eq!(parse_reset_duration("250ms"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_reset_duration("1.5s"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_reset_duration("2"), Some(Duration::from_secs(2)));
+        assert_eq!(parse_reset_duration("soon"), None);
+    }
+
+    #[test]
+    fn test_exponential_backoff_without_jitter() {
+        let policy = RetryPolicy::new()
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(350))
+            .jitter(0.0);
+
+        assert_eq!(policy.delay_for(1, None), Duration::from_millis(100));
+        assert_eq!(policy.delay_for(2, None), Duration::from_millis(200));
+        assert_eq!(policy.delay_for(3, None), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_server_hints_take_precedence() {
+        let policy = RetryPolicy::new().jitter(0.0);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-reset-requests", HeaderValue::from_static("2s"));
+        headers.insert("x-ratelimit-reset-tokens", HeaderValue::from_static("500ms"));
+        assert_eq!(policy.delay_for(1, Some(&headers)), Duration::from_secs(2));
+
+        headers.insert("retry-after", HeaderValue::from_static("3"));
+        assert_eq!(policy.delay_for(1, Some(&headers)), Duration::from_secs(3));
+
+        headers.insert("retry-after-ms", HeaderValue::from_static("1200"));
+        assert_eq!(policy.delay_for(1, Some(&headers)), Duration::from_millis(1200));
+    }
+
+    #[test]
+    fn test_retryable_error_types_are_matched_from_body() {
+        let policy = RetryPolicy::new().retry_statuses(vec![]);
+        let body = r#"{"error":{"message":"overloaded","type":"server_error"}}"#;
+
+        assert!(policy.is_retryable_response(400, body));
+        assert!(!policy.is_retryable_response(400, r#"{"error":{"message":"bad","type":"invalid_request"}}"#));
+    }
+}
@@ -0,0 +1,505 @@
+use futures::stream::{self, BoxStream};
+use futures::{Stream, StreamExt};
+use std::collections::VecDeque;
+use thiserror::Error;
+
+use crate::accumulator::StreamAccumulator;
+use crate::client::ClientError;
+use crate::responses_client::ResponsesClient;
+use crate::streaming::{ResponseStream, StreamingError};
+use crate::tool_registry::{ExecuteOptions, ToolRegistry};
+use crate::types::{
+    CreateResponseBody, Input, Item, MessageContent, ResponseResource, ResponseStatus,
+    StreamingEvent, Tool, Usage,
+};
+
+const DEFAULT_MAX_TURNS: u32 = 10;
+
+/// How tool outputs are sent back to the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConversationMode {
+    /// Every turn replays the whole transcript as `input`. Works with
+    /// `store: false` and servers that don't keep responses.
+    #[default]
+    Stateless,
+    /// Every turn sends only the new tool outputs and chains onto the previous
+    /// response with `previous_response_id`. Requires the server to store
+    /// responses.
+    Stateful,
+}
+
+#[derive(Error, Debug)]
+pub enum RunnerError {
+    #[error(transparent)]
+    Client(#[from] ClientError),
+
+    #[error(transparent)]
+    Stream(#[from] StreamingError),
+
+    /// The model was still calling tools after `max_turns` responses.
+    #[error("Run stopped after {max_turns} turns with tool calls still pending")]
+    MaxTurns {
+        max_turns: u32,
+        result: Box<RunResult>,
+    },
+
+    /// Executing the latest tool calls would exceed `max_tool_calls`. They
+    /// are left unanswered in `result`.
+    #[error("Run stopped before exceeding {max_tool_calls} tool calls")]
+    MaxToolCalls {
+        max_tool_calls: u32,
+        result: Box<RunResult>,
+    },
+
+    /// The cancellation token of the [`ExecuteOptions`] fired while tools
+    /// were running. `result` ends with the outputs of the interrupted calls.
+    #[error("Run was cancelled")]
+    Cancelled { result: Box<RunResult> },
+}
+
+/// Everything that happened during a [`Runner::run`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunResult {
+    /// The initial input followed by every output item and tool output, in
+    /// the order the model saw them.
+    pub transcript: Vec<Item>,
+    /// One response per turn, oldest first.
+    pub responses: Vec<ResponseResource>,
+    /// Sum of the usage of all responses, if any reported it.
+    pub usage: Option<Usage>,
+    /// Number of tool calls executed.
+    pub tool_calls: u32,
+}
+
+impl RunResult {
+    fn new(transcript: Vec<Item>) -> Self {
+        Self {
+            transcript,
+            responses: Vec::new(),
+            usage: None,
+            tool_calls: 0,
+        }
+    }
+
+    fn push_response(&mut self, response: ResponseResource) {
+        if let Some(usage) = &response.usage {
+            match &mut self.usage {
+                Some(total) => *total += usage,
+                None => self.usage = Some(usage.clone()),
+            }
+        }
+        self.transcript.extend(response.output.iter().cloned());
+        self.responses.push(response);
+    }
+
+    /// The last response, which holds the final answer when the run succeeded.
+    pub fn final_response(&self) -> Option<&ResponseResource> {
+        self.responses.last()
+    }
+
+    /// Text of the `output_text` parts of the final response.
+    pub fn output_text(&self) -> String {
+        self.final_response()
+            .into_iter()
+            .flat_map(|response| &response.output)
+            .filter_map(|item| match item {
+                Item::Message { content, .. } => Some(content),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|part| match part {
+                MessageContent::OutputText { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Events yielded by [`Runner::run_streamed`], in the order they happened.
+/// `turn` is the zero-based index of the response within the run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunEvent {
+    /// The stream for the next response was opened.
+    TurnStarted { turn: u32 },
+    /// An event of the turn's response stream, forwarded unchanged.
+    Event { turn: u32, event: StreamingEvent },
+    /// The turn's response reached a terminal event.
+    TurnFinished { turn: u32, response: ResponseResource },
+    /// A function call of the turn is about to be executed.
+    ToolCallStarted { turn: u32, call: Item },
+    /// A function call was executed. `output` is the item sent back to the model.
+    ToolCallFinished { turn: u32, output: Item },
+    /// The last event of a run that ended without an error.
+    Finished(RunResult),
+}
+
+/// Drives a conversation until the model stops calling tools.
+///
+/// Each turn sends the request, executes the function calls in the response
+/// with the [`ToolRegistry`] and feeds the outputs back. The run ends with
+/// the first response that has no function calls or did not complete.
+///
+/// ```rust,no_run
+/// use openresponses_rust::{CreateResponseBody, Input, ResponsesClient, Runner, ToolRegistry};
+///
+/// # async fn run(registry: ToolRegistry) -> Result<(), Box<dyn std::error::Error>> {
+/// let runner = Runner::new(ResponsesClient::new("sk-..."), registry).max_turns(5);
+/// let result = runner
+///     .run(CreateResponseBody {
+///         model: Some("gpt-4o".to_string()),
+///         input: Some(Input::Single("What's the weather in Seoul?".to_string())),
+///         ..Default::default()
+///     })
+///     .await?;
+/// println!("{}", result.output_text());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Runner {
+    client: ResponsesClient,
+    registry: ToolRegistry,
+    mode: ConversationMode,
+    max_turns: u32,
+    max_tool_calls: Option<u32>,
+    execute_options: ExecuteOptions,
+}
+
+impl Runner {
+    pub fn new(client: impl Into<ResponsesClient>, registry: ToolRegistry) -> Self {
+        Self {
+            client: client.into(),
+            registry,
+            mode: ConversationMode::default(),
+            max_turns: DEFAULT_MAX_TURNS,
+            max_tool_calls: None,
+            execute_options: ExecuteOptions::default(),
+        }
+    }
+
+    pub fn mode(mut self, mode: ConversationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Maximum number of requests per run. Defaults to 10.
+    pub fn max_turns(mut self, max_turns: u32) -> Self {
+        self.max_turns = max_turns;
+        self
+    }
+
+    /// Maximum number of tool calls executed per run. Defaults to the
+    /// request's `max_tool_calls`, which then bounds the whole run rather than
+    /// each response; unlimited if neither is set. When set, the request's
+    /// value is sent unchanged as the per-response cap.
+    pub fn max_tool_calls(mut self, max_tool_calls: u32) -> Self {
+        self.max_tool_calls = Some(max_tool_calls);
+        self
+    }
+
+    /// How the function calls of a response are executed. Calls run
+    /// concurrently by default, and one at a time when the request sets
+    /// `parallel_tool_calls: false`.
+    pub fn execute_options(mut self, options: ExecuteOptions) -> Self {
+        self.execute_options = options;
+        self
+    }
+
+    pub fn registry(&self) -> &ToolRegistry {
+        &self.registry
+    }
+
+    /// Runs `request` to completion. Registered tools are added to
+    /// `request.tools` unless a tool with the same name is already declared.
+    pub async fn run(&self, request: CreateResponseBody) -> Result<RunResult, RunnerError> {
+        let mut state = RunState::new(self, request);
+
+        loop {
+            let request = state.next_request.take().expect("a request is queued");
+            let response = self.client.create_response(request).await?;
+            let calls = function_calls(&response);
+            let finished = calls.is_empty() || response.status != ResponseStatus::Completed;
+            state.result.push_response(response);
+
+            if finished {
+                return Ok(state.result);
+            }
+            state.check_limits(self, calls.len() as u32)?;
+
+            let outputs = self.registry.execute_all_with(&calls, &state.options).await;
+            state.queue_follow_up(self, outputs);
+            state.check_cancelled()?;
+        }
+    }
+
+    /// Streaming variant of [`run`](Self::run).
+    ///
+    /// Every turn is streamed, its events are forwarded as they arrive and
+    /// the function calls of a completed response are executed before the
+    /// next stream is opened, so all events of one turn precede those of the
+    /// next. Within a turn every call is announced with
+    /// [`RunEvent::ToolCallStarted`] before the outputs follow in call order.
+    /// The stream ends after [`RunEvent::Finished`] or the first error.
+    pub fn run_streamed(
+        &self,
+        request: CreateResponseBody,
+    ) -> impl Stream<Item = Result<RunEvent, RunnerError>> + Send + Unpin + use<> {
+        let run = StreamedRun {
+            state: RunState::new(self, request),
+            runner: self.clone(),
+            phase: Phase::Request,
+            queued: VecDeque::new(),
+        };
+        Box::pin(stream::unfold(run, |mut run| async move {
+            let event = run.next_event().await?;
+            Some((event, run))
+        }))
+    }
+}
+
+enum Phase {
+    Request,
+    Streaming {
+        stream: ResponseStream,
+        accumulator: Box<StreamAccumulator>,
+    },
+    Tools {
+        turn: u32,
+        unannounced: VecDeque<Item>,
+        running: BoxStream<'static, Item>,
+        outputs: Vec<Item>,
+    },
+    Done,
+}
+
+struct StreamedRun {
+    runner: Runner,
+    state: RunState,
+    phase: Phase,
+    queued: VecDeque<Result<RunEvent, RunnerError>>,
+}
+
+impl StreamedRun {
+    async fn next_event(&mut self) -> Option<Result<RunEvent, RunnerError>> {
+        loop {
+            if let Some(event) = self.queued.pop_front() {
+                return Some(event);
+            }
+
+            let turn = self.state.result.responses.len() as u32;
+            match std::mem::replace(&mut self.phase, Phase::Done) {
+                Phase::Request => {
+                    let request = self.state.next_request.take().expect("a request is queued");
+                    let stream = match self.runner.client.stream_response(request).await {
+                        Ok(stream) => stream,
+                        Err(error) => return Some(Err(error.into())),
+                    };
+                    self.phase = Phase::Streaming {
+                        stream,
+                        accumulator: Box::default(),
+                    };
+                    return Some(Ok(RunEvent::TurnStarted { turn }));
+                }
+                Phase::Streaming {
+                    mut stream,
+                    mut accumulator,
+                } => {
+                    let event = match stream.next().await {
+                        Some(Ok(event)) => event,
+                        Some(Err(error)) => return Some(Err(error.into())),
+                        None => {
+                            let error = StreamingError::LifecycleError {
+                                message: "Stream ended before a terminal response event"
+                                    .to_string(),
+                            };
+                            return Some(Err(error.into()));
+                        }
+                    };
+                    if let Err(error) = accumulator.apply(&event) {
+                        return Some(Err(StreamingError::from(error).into()));
+                    }
+
+                    if accumulator.is_finished() {
+                        let response = accumulator
+                            .into_response()
+                            .expect("a finished stream has a response");
+                        self.finish_turn(turn, response);
+                    } else {
+                        self.phase = Phase::Streaming { stream, accumulator };
+                    }
+                    return Some(Ok(RunEvent::Event { turn, event }));
+                }
+                Phase::Tools {
+                    turn,
+                    mut unannounced,
+                    mut running,
+                    mut outputs,
+                } => {
+                    let event = if let Some(call) = unannounced.pop_front() {
+                        RunEvent::ToolCallStarted { turn, call }
+                    } else if let Some(output) = running.next().await {
+                        outputs.push(output.clone());
+                        RunEvent::ToolCallFinished { turn, output }
+                    } else {
+                        self.state.queue_follow_up(&self.runner, outputs);
+                        if let Err(error) = self.state.check_cancelled() {
+                            return Some(Err(error));
+                        }
+                        self.phase = Phase::Request;
+                        continue;
+                    };
+                    self.phase = Phase::Tools {
+                        turn,
+                        unannounced,
+                        running,
+                        outputs,
+                    };
+                    return Some(Ok(event));
+                }
+                Phase::Done => return None,
+            }
+        }
+    }
+
+    fn finish_turn(&mut self, turn: u32, response: ResponseResource) {
+        let calls = function_calls(&response);
+        let finished = calls.is_empty() || response.status != ResponseStatus::Completed;
+        self.state.result.push_response(response.clone());
+        self.queued.push_back(Ok(RunEvent::TurnFinished { turn, response }));
+
+        if finished {
+            let result = self.state.take_result();
+            self.queued.push_back(Ok(RunEvent::Finished(result)));
+        } else if let Err(error) = self.state.check_limits(&self.runner, calls.len() as u32) {
+            self.queued.push_back(Err(error));
+        } else {
+            self.phase = Phase::Tools {
+                turn,
+                running: self.runner.registry.execute_ordered(&calls, &self.state.options),
+                unannounced: calls.into(),
+                outputs: Vec::new(),
+            };
+        }
+    }
+}
+
+struct RunState {
+    base: CreateResponseBody,
+    options: ExecuteOptions,
+    max_tool_calls: Option<u32>,
+    result: RunResult,
+    next_request: Option<CreateResponseBody>,
+}
+
+impl RunState {
+    fn new(runner: &Runner, mut request: CreateResponseBody) -> Self {
+        let mut tools = request.tools.take().unwrap_or_default();
+        for tool in runner.registry.tools() {
+            if !tools.iter().any(|declared| same_function(declared, &tool)) {
+                tools.push(tool);
+            }
+        }
+        if !tools.is_empty() {
+            request.tools = Some(tools);
+        }
+
+        let transcript = match &request.input {
+            Some(Input::Single(text)) => vec![Item::user_message(text.clone())],
+            Some(Input::Items(items)) => items.clone(),
+            None => Vec::new(),
+        };
+
+        let mut options = runner.execute_options.clone();
+        if request.parallel_tool_calls == Some(false) {
+            options.parallel = false;
+        }
+
+        let max_tool_calls = runner.max_tool_calls.or_else(|| {
+            request
+                .max_tool_calls
+                .map(|max_tool_calls| max_tool_calls.max(0) as u32)
+        });
+
+        Self {
+            base: request.clone(),
+            options,
+            max_tool_calls,
+            result: RunResult::new(transcript),
+            next_request: Some(request),
+        }
+    }
+
+    fn check_limits(&mut self, runner: &Runner, pending: u32) -> Result<(), RunnerError> {
+        if let Some(max_tool_calls) = self.max_tool_calls {
+            if self.result.tool_calls + pending > max_tool_calls {
+                return Err(RunnerError::MaxToolCalls {
+                    max_tool_calls,
+                    result: Box::new(self.take_result()),
+                });
+            }
+        }
+        if self.result.responses.len() as u32 >= runner.max_turns {
+            return Err(RunnerError::MaxTurns {
+                max_turns: runner.max_turns,
+                result: Box::new(self.take_result()),
+            });
+        }
+        Ok(())
+    }
+
+    fn check_cancelled(&mut self) -> Result<(), RunnerError> {
+        if self.options.is_cancelled() {
+            return Err(RunnerError::Cancelled {
+                result: Box::new(self.take_result()),
+            });
+        }
+        Ok(())
+    }
+
+    fn queue_follow_up(&mut self, runner: &Runner, outputs: Vec<Item>) {
+        self.result.tool_calls += outputs.len() as u32;
+        self.result.transcript.extend(outputs.iter().cloned());
+
+        let mut request = self.base.clone();
+        if runner.max_tool_calls.is_none() {
+            if let Some(max_tool_calls) = &mut request.max_tool_calls {
+                // The server applies it per response; pass on what is left of the run's budget.
+                let used = i32::try_from(self.result.tool_calls).unwrap_or(i32::MAX);
+                *max_tool_calls = max_tool_calls.saturating_sub(used).max(0);
+            }
+        }
+        match runner.mode {
+            ConversationMode::Stateless => {
+                request.input = Some(Input::Items(self.result.transcript.clone()));
+            }
+            ConversationMode::Stateful => {
+                request.input = Some(Input::Items(outputs));
+                request.previous_response_id = self
+                    .result
+                    .final_response()
+                    .map(|response| response.id.clone());
+            }
+        }
+        self.next_request = Some(request);
+    }
+
+    fn take_result(&mut self) -> RunResult {
+        std::mem::replace(&mut self.result, RunResult::new(Vec::new()))
+    }
+}
+
+fn function_calls(response: &ResponseResource) -> Vec<Item> {
+    response
+        .output
+        .iter()
+        .filter(|item| matches!(item, Item::FunctionCall { .. }))
+        .cloned()
+        .collect()
+}
+
+fn same_function(a: &Tool, b: &Tool) -> bool {
+    matches!(
+        (a, b),
+        (Tool::Function { name: a, .. }, Tool::Function { name: b, .. }) if a == b
+    )
+}
@@ -0,0 +1,148 @@
+use serde_json::{Map, Value, json};
+
+/// String formats accepted in strict mode. Any other `format` is dropped.
+const STRICT_FORMATS: &[&str] = &[
+    "date-time", "time", "date", "duration", "email", "hostname", "ipv4", "ipv6", "uuid",
+];
+
+/// Keywords strict mode rejects, removed from every subschema.
+const UNSUPPORTED_KEYWORDS: &[&str] = &["$schema", "$id", "title", "default", "examples"];
+
+/// Rewrites a JSON Schema to satisfy strict function calling:
+///
+/// - every object lists all of its properties in `required` and sets
+///   `additionalProperties: false`; properties that were not required become
+///   nullable so they stay optional;
+/// - `oneOf` becomes `anyOf`;
+/// - `$schema`, `$id`, `title`, `default`, `examples` and unsupported
+///   `format`s are removed.
+///
+/// Property names are left alone, so a property called `title` survives.
+pub fn strict_schema(mut schema: Value) -> Value {
+    make_strict(&mut schema);
+    schema
+}
+
+fn make_strict(schema: &mut Value) {
+    let Value::Object(object) = schema else {
+        return;
+    };
+
+    for keyword in UNSUPPORTED_KEYWORDS {
+        object.remove(*keyword);
+    }
+    if let Some(Value::String(format)) = object.get("format") {
+        if !STRICT_FORMATS.contains(&format.as_str()) {
+            object.remove("format");
+        }
+    }
+    if let Some(one_of) = object.remove("oneOf") {
+        object.insert("anyOf".to_string(), one_of);
+    }
+
+    if is_object_schema(object) {
+        let previously_required: Vec<String> = match object.get("required") {
+            Some(Value::Array(required)) => required
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        };
+        let properties = object
+            .entry("properties")
+            .or_insert_with(|| Value::Object(Map::new()));
+        let required = match properties {
+            Value::Object(properties) => {
+                for (name, property) in properties.iter_mut() {
+                    if !previously_required.contains(name) {
+                        make_nullable(property);
+                    }
+                }
+                properties.keys().cloned().map(Value::String).collect()
+            }
+            _ => Vec::new(),
+        };
+        object.insert("required".to_string(), Value::Array(required));
+        object.insert("additionalProperties".to_string(), Value::Bool(false));
+    }
+
+    for (keyword, child) in object.iter_mut() {
+        match (keyword.as_str(), child) {
+            ("properties" | "$defs" | "definitions", Value::Object(schemas)) => {
+                schemas.values_mut().for_each(make_strict);
+            }
+            ("anyOf" | "allOf" | "prefixItems", Value::Array(schemas)) => {
+                schemas.iter_mut().for_each(make_strict);
+            }
+            ("items" | "not" | "additionalProperties", child) => make_strict(child),
+            _ => {}
+        }
+    }
+}
+
+/// Lets `schema` also accept `null`, the strict-mode spelling of an
+/// optional property.
+fn make_nullable(schema: &mut Value) {
+    let null = Value::String("null".to_string());
+    if let Value::Object(object) = schema {
+        match object.get_mut("type") {
+            Some(Value::Array(types)) => {
+                if !types.contains(&null) {
+                    types.push(null);
+                }
+                return allow_null_in_enum(object);
+            }
+            Some(kind @ Value::String(_)) => {
+                if *kind != null {
+                    *kind = Value::Array(vec![kind.take(), null]);
+                }
+                return allow_null_in_enum(object);
+            }
+            _ => {}
+        }
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(Value::Array(variants)) = object.get_mut(keyword) {
+                let null_variant = json!({"type": "null"});
+                if !variants.contains(&null_variant) {
+                    variants.push(null_variant);
+                }
+                return;
+            }
+        }
+        if object.is_empty() {
+            return;
+        }
+    }
+    let original = schema.take();
+    *schema = json!({"anyOf": [original, {"type": "null"}]});
+}
+
+fn allow_null_in_enum(object: &mut Map<String, Value>) {
+    if let Some(Value::Array(values)) = object.get_mut("enum") {
+        if !values.contains(&Value::Null) {
+            values.push(Value::Null);
+        }
+    }
+}
+
+/// Objects with fixed properties. Maps (`additionalProperties` holding a
+/// schema) are left open since strict mode cannot express them.
+fn is_object_schema(object: &Map<String, Value>) -> bool {
+    let is_object = match object.get("type") {
+        Some(Value::String(kind)) => kind == "object",
+        Some(Value::Array(kinds)) => kinds.iter().any(|kind| kind == "object"),
+        _ => object.contains_key("properties"),
+    };
+    is_object && !matches!(object.get("additionalProperties"), Some(Value::Object(_)))
+}
+
+/// The inlined, strict parameters schema of `T`.
+#[cfg(feature = "schemars")]
+pub(crate) fn parameters_for<T: schemars::JsonSchema>() -> Value {
+    let schema = schemars::generate::SchemaSettings::draft2020_12()
+        .with(|settings| settings.inline_subschemas = true)
+        .into_generator()
+        .into_root_schema_for::<T>();
+    strict_schema(schema.to_value())
+}
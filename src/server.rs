@@ -0,0 +1,582 @@
+//! Serving the Open Responses API on top of a [`ResponsesProvider`].
+//!
+//! Requires the `server` feature.
+//!
+//! ```rust,no_run
+//! use openresponses_rust::server::{self, ProviderError, ProviderResponse, ResponsesProvider};
+//! use openresponses_rust::CreateResponseBody;
+//!
+//! struct Echo;
+//!
+//! #[async_trait::async_trait]
+//! impl ResponsesProvider for Echo {
+//!     async fn create_response(
+//!         &self,
+//!         request: CreateResponseBody,
+//!     ) -> Result<ProviderResponse, ProviderError> {
+//!         Err(ProviderError::server_error("not implemented"))
+//!     }
+//! }
+//!
+//! # async fn run() -> std::io::Result<()> {
+//! let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await?;
+//! server::serve(listener, Echo).await
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use axum::Router;
+use axum::body::{Body, Bytes};
+use axum::extract::State;
+use axum::http::header::{ACCEPT, CACHE_CONTROL, CONTENT_TYPE};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Json, Response};
+use axum::routing::post;
+use futures::{Stream, StreamExt, stream};
+use std::pin::Pin;
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::net::TcpListener;
+
+use crate::accumulator::StreamAccumulator;
+use crate::sse::SseEncoder;
+use crate::types::{
+    ApiErrorDetail, ApiErrorResponse, CreateResponseBody, ErrorType, FunctionCallStatus, Item,
+    MessageContent, MessageStatus, ResponseResource, ResponseStatus, StreamingEvent,
+};
+
+const EVENT_STREAM: &str = "text/event-stream";
+const JSON: &str = "application/json";
+
+/// Backend behind [`router`] and [`serve`].
+#[async_trait]
+pub trait ResponsesProvider: Send + Sync + 'static {
+    /// Handles `POST /v1/responses`. Either variant of [`ProviderResponse`] can
+    /// be returned regardless of `request.stream`; the server converts between
+    /// them as needed.
+    async fn create_response(
+        &self,
+        request: CreateResponseBody,
+    ) -> Result<ProviderResponse, ProviderError>;
+}
+
+pub type EventStream = Pin<Box<dyn Stream<Item = StreamingEvent> + Send>>;
+
+pub enum ProviderResponse {
+    Response(Box<ResponseResource>),
+    /// Events without sequence numbers or `[DONE]`; both are added by the server.
+    Stream(EventStream),
+}
+
+impl ProviderResponse {
+    pub fn stream<S>(events: S) -> Self
+    where
+        S: Stream<Item = StreamingEvent> + Send + 'static,
+    {
+        ProviderResponse::Stream(Box::pin(events))
+    }
+}
+
+impl From<ResponseResource> for ProviderResponse {
+    fn from(response: ResponseResource) -> Self {
+        ProviderResponse::Response(Box::new(response))
+    }
+}
+
+/// An error returned to the client as an `ApiErrorResponse` body.
+#[derive(Error, Debug, Clone)]
+#[error("{} ({status})", error.message)]
+pub struct ProviderError {
+    pub status: StatusCode,
+    pub error: ApiErrorDetail,
+}
+
+impl ProviderError {
+    pub fn new(status: StatusCode, error_type: ErrorType, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            error: ApiErrorDetail {
+                message: message.into(),
+                error_type: Some(error_type.as_str().to_string()),
+                param: None,
+                code: None,
+            },
+        }
+    }
+
+    pub fn invalid_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, ErrorType::InvalidRequest, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, ErrorType::NotFound, message)
+    }
+
+    pub fn too_many_requests(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::TOO_MANY_REQUESTS, ErrorType::TooManyRequests, message)
+    }
+
+    pub fn model_error(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, ErrorType::ModelError, message)
+    }
+
+    pub fn server_error(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, ErrorType::ServerError, message)
+    }
+
+    pub fn param(mut self, param: impl Into<String>) -> Self {
+        self.error.param = Some(param.into());
+        self
+    }
+
+    pub fn code(mut self, code: impl Into<String>) -> Self {
+        self.error.code = Some(code.into());
+        self
+    }
+}
+
+impl IntoResponse for ProviderError {
+    fn into_response(self) -> Response {
+        (self.status, Json(ApiErrorResponse { error: self.error })).into_response()
+    }
+}
+
+/// Routes `POST /v1/responses` to `provider`. Merge it into a larger app to
+/// add middleware or other routes.
+pub fn router<P: ResponsesProvider>(provider: P) -> Router {
+    Router::new()
+        .route("/v1/responses", post(create_response::<P>))
+        .with_state(Arc::new(provider))
+}
+
+pub async fn serve<P: ResponsesProvider>(listener: TcpListener, provider: P) -> std::io::Result<()> {
+    axum::serve(listener, router(provider)).await
+}
+
+async fn create_response<P: ResponsesProvider>(
+    State(provider): State<Arc<P>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let request: CreateResponseBody = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(error) => {
+            return ProviderError::invalid_request(format!("Invalid request body: {error}"))
+                .into_response();
+        }
+    };
+
+    let streaming = request.stream == Some(true);
+    let content_type = if streaming { EVENT_STREAM } else { JSON };
+    if !accepts(&headers, content_type) {
+        return ProviderError::new(
+            StatusCode::NOT_ACCEPTABLE,
+            ErrorType::InvalidRequest,
+            format!("This request can only be answered with `{content_type}`"),
+        )
+        .into_response();
+    }
+
+    let response = match provider.create_response(request).await {
+        Ok(response) => response,
+        Err(error) => return error.into_response(),
+    };
+
+    match (response, streaming) {
+        (ProviderResponse::Response(response), false) => Json(*response).into_response(),
+        (ProviderResponse::Response(response), true) => match response.status {
+            ResponseStatus::Completed | ResponseStatus::Failed | ResponseStatus::Incomplete => {
+                sse_response(Box::pin(stream::iter(response_events(&response))))
+            }
+            status => ProviderError::server_error(format!(
+                "Provider returned a `{status}` response, which cannot be streamed"
+            ))
+            .into_response(),
+        },
+        (ProviderResponse::Stream(events), true) => sse_response(events),
+        (ProviderResponse::Stream(events), false) => match collect(events).await {
+            Ok(response) => Json(response).into_response(),
+            Err(error) => error.into_response(),
+        },
+    }
+}
+
+/// An event that breaks the streaming rules ends the body with an error,
+/// which aborts the connection.
+fn sse_response(events: EventStream) -> Response {
+    event_stream_response(Body::from_stream(SseEncoder::new().into_stream(events)))
+}
+
+pub(crate) fn event_stream_response(body: Body) -> Response {
+    let mut response = body.into_response();
+    let headers = response.headers_mut();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static(EVENT_STREAM));
+    headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    response
+}
+
+/// Generates the event sequence a conforming server would stream for
+/// `response`, without sequence numbers or `[DONE]`.
+///
+/// Items without an id get `item_<output_index>` and items without a status
+/// are reported as completed. Text, refusals, arguments and reasoning are
+/// streamed as word-sized deltas. Only completed, failed and incomplete
+/// responses end with a terminal event.
+pub fn response_events(response: &ResponseResource) -> Vec<StreamingEvent> {
+    let mut response = response.clone();
+    for (index, item) in response.output.iter_mut().enumerate() {
+        complete_item(item, index);
+    }
+
+    let mut started = response.clone();
+    started.status = ResponseStatus::InProgress;
+    started.completed_at = None;
+    started.output.clear();
+    started.usage = None;
+
+    let mut events = vec![
+        StreamingEvent::ResponseCreated {
+            sequence_number: 0,
+            response: started.clone(),
+        },
+        StreamingEvent::ResponseInProgress {
+            sequence_number: 0,
+            response: started,
+        },
+    ];
+
+    for (index, item) in response.output.iter().enumerate() {
+        item_events(&mut events, index as i32, item);
+    }
+
+    let terminal = match response.status {
+        ResponseStatus::Completed => StreamingEvent::ResponseCompleted {
+            sequence_number: 0,
+            response,
+        },
+        ResponseStatus::Failed => StreamingEvent::ResponseFailed {
+            sequence_number: 0,
+            response,
+        },
+        ResponseStatus::Incomplete => StreamingEvent::ResponseIncomplete {
+            sequence_number: 0,
+            response,
+        },
+        _ => return events,
+    };
+    events.push(terminal);
+    events
+}
+
+fn complete_item(item: &mut Item, index: usize) {
+    match item {
+        Item::Message { id, status, .. } | Item::Reasoning { id, status, .. } => {
+            id.get_or_insert_with(|| format!("item_{index}"));
+            status.get_or_insert(MessageStatus::Completed);
+        }
+        Item::FunctionCall { id, status, .. } => {
+            id.get_or_insert_with(|| format!("item_{index}"));
+            status.get_or_insert(FunctionCallStatus::Completed);
+        }
+        _ => {}
+    }
+}
+
+fn item_events(events: &mut Vec<StreamingEvent>, output_index: i32, item: &Item) {
+    events.push(StreamingEvent::OutputItemAdded {
+        sequence_number: 0,
+        output_index,
+        item: Some(skeleton(item)),
+    });
+
+    match item {
+        Item::Message {
+            id: Some(item_id),
+            content,
+            ..
+        } => {
+            for (content_index, part) in content.iter().enumerate() {
+                message_part_events(events, item_id, output_index, content_index as i32, part);
+            }
+        }
+        Item::FunctionCall {
+            id: Some(item_id),
+            arguments,
+            ..
+        } => {
+            for delta in deltas(arguments) {
+                events.push(StreamingEvent::FunctionCallArgumentsDelta {
+                    sequence_number: 0,
+                    item_id: item_id.clone(),
+                    output_index,
+                    delta,
+                    obfuscation: None,
+                });
+            }
+            events.push(StreamingEvent::FunctionCallArgumentsDone {
+                sequence_number: 0,
+                item_id: item_id.clone(),
+                output_index,
+                arguments: arguments.clone(),
+            });
+        }
+        Item::Reasoning {
+            id: Some(item_id),
+            content,
+            summary,
+            ..
+        } => {
+            for (summary_index, part) in summary.iter().enumerate() {
+                summary_part_events(events, item_id, output_index, summary_index as i32, part);
+            }
+            for (content_index, part) in content.iter().flatten().enumerate() {
+                message_part_events(events, item_id, output_index, content_index as i32, part);
+            }
+        }
+        _ => {}
+    }
+
+    events.push(StreamingEvent::OutputItemDone {
+        sequence_number: 0,
+        output_index,
+        item: Some(item.clone()),
+    });
+}
+
+/// The item as announced by `response.output_item.added`: in progress, with
+/// no content streamed yet.
+fn skeleton(item: &Item) -> Item {
+    let mut item = item.clone();
+    match &mut item {
+        Item::Message {
+            status, content, ..
+        } => {
+            *status = Some(MessageStatus::InProgress);
+            content.clear();
+        }
+        Item::FunctionCall {
+            status, arguments, ..
+        } => {
+            *status = Some(FunctionCallStatus::InProgress);
+            arguments.clear();
+        }
+        Item::Reasoning {
+            status,
+            content,
+            summary,
+            ..
+        } => {
+            *status = Some(MessageStatus::InProgress);
+            *content = content.as_ref().map(|_| Vec::new());
+            summary.clear();
+        }
+        _ => {}
+    }
+    item
+}
+
+fn message_part_events(
+    events: &mut Vec<StreamingEvent>,
+    item_id: &str,
+    output_index: i32,
+    content_index: i32,
+    part: &MessageContent,
+) {
+    let empty = match part {
+        MessageContent::OutputText { .. } => MessageContent::output_text(""),
+        MessageContent::Refusal { .. } => MessageContent::refusal(""),
+        MessageContent::ReasoningText { .. } => MessageContent::reasoning(""),
+        other => other.clone(),
+    };
+    events.push(StreamingEvent::ContentPartAdded {
+        sequence_number: 0,
+        item_id: item_id.to_string(),
+        output_index,
+        content_index,
+        part: empty,
+    });
+
+    match part {
+        MessageContent::OutputText {
+            text, annotations, ..
+        } => {
+            for delta in deltas(text) {
+                events.push(StreamingEvent::OutputTextDelta {
+                    sequence_number: 0,
+                    item_id: item_id.to_string(),
+                    output_index,
+                    content_index,
+                    delta,
+                    logprobs: None,
+                    obfuscation: None,
+                });
+            }
+            for (annotation_index, annotation) in annotations.iter().enumerate() {
+                events.push(StreamingEvent::OutputTextAnnotationAdded {
+                    sequence_number: 0,
+                    item_id: item_id.to_string(),
+                    output_index,
+                    content_index,
+                    annotation_index: annotation_index as i32,
+                    annotation: Some(annotation.clone()),
+                });
+            }
+            events.push(StreamingEvent::OutputTextDone {
+                sequence_number: 0,
+                item_id: item_id.to_string(),
+                output_index,
+                content_index,
+                text: text.clone(),
+                logprobs: None,
+            });
+        }
+        MessageContent::Refusal { refusal } => {
+            for delta in deltas(refusal) {
+                events.push(StreamingEvent::RefusalDelta {
+                    sequence_number: 0,
+                    item_id: item_id.to_string(),
+                    output_index,
+                    content_index,
+                    delta,
+                });
+            }
+            events.push(StreamingEvent::RefusalDone {
+                sequence_number: 0,
+                item_id: item_id.to_string(),
+                output_index,
+                content_index,
+                refusal: refusal.clone(),
+            });
+        }
+        MessageContent::ReasoningText { text } => {
+            for delta in deltas(text) {
+                events.push(StreamingEvent::ReasoningDelta {
+                    sequence_number: 0,
+                    item_id: item_id.to_string(),
+                    output_index,
+                    content_index,
+                    delta,
+                    obfuscation: None,
+                });
+            }
+            events.push(StreamingEvent::ReasoningDone {
+                sequence_number: 0,
+                item_id: item_id.to_string(),
+                output_index,
+                content_index,
+                text: text.clone(),
+            });
+        }
+        _ => {}
+    }
+
+    events.push(StreamingEvent::ContentPartDone {
+        sequence_number: 0,
+        item_id: item_id.to_string(),
+        output_index,
+        content_index,
+        part: part.clone(),
+    });
+}
+
+fn summary_part_events(
+    events: &mut Vec<StreamingEvent>,
+    item_id: &str,
+    output_index: i32,
+    summary_index: i32,
+    part: &MessageContent,
+) {
+    let text = match part {
+        MessageContent::SummaryText { text } => text.as_str(),
+        _ => "",
+    };
+    events.push(StreamingEvent::ReasoningSummaryPartAdded {
+        sequence_number: 0,
+        item_id: item_id.to_string(),
+        output_index,
+        summary_index,
+        part: MessageContent::summary(""),
+    });
+    for delta in deltas(text) {
+        events.push(StreamingEvent::ReasoningSummaryDelta {
+            sequence_number: 0,
+            item_id: item_id.to_string(),
+            output_index,
+            summary_index,
+            delta,
+            obfuscation: None,
+        });
+    }
+    events.push(StreamingEvent::ReasoningSummaryDone {
+        sequence_number: 0,
+        item_id: item_id.to_string(),
+        output_index,
+        summary_index,
+        text: text.to_string(),
+    });
+    events.push(StreamingEvent::ReasoningSummaryPartDone {
+        sequence_number: 0,
+        item_id: item_id.to_string(),
+        output_index,
+        summary_index,
+        part: part.clone(),
+    });
+}
+
+fn deltas(text: &str) -> Vec<String> {
+    text.split_inclusive(' ').map(str::to_string).collect()
+}
+
+async fn collect(mut events: EventStream) -> Result<ResponseResource, ProviderError> {
+    let mut accumulator = StreamAccumulator::new();
+    let mut stream_error = None;
+
+    while let Some(event) = events.next().await {
+        if let StreamingEvent::Error { error, .. } = &event {
+            stream_error = Some(error.message.clone());
+        }
+        match accumulator.apply(&event) {
+            Ok(Some(response)) => return Ok(response.clone()),
+            Ok(None) => {}
+            Err(error) => return Err(ProviderError::server_error(error.to_string())),
+        }
+    }
+
+    Err(ProviderError::server_error(stream_error.unwrap_or_else(|| {
+        "Provider stream ended before a terminal response event".to_string()
+    })))
+}
+
+fn accepts(headers: &HeaderMap, content_type: &str) -> bool {
+    let Some(accept) = headers.get(ACCEPT).and_then(|value| value.to_str().ok()) else {
+        return true;
+    };
+    let any_subtype = content_type
+        .split_once('/')
+        .map(|(kind, _)| format!("{kind}/*"));
+
+    // The most specific matching media range decides, and `q=0` refuses it.
+    accept
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';').map(str::trim);
+            let media_range = parts.next()?;
+            let specificity = if media_range == content_type {
+                2
+            } else if Some(media_range) == any_subtype.as_deref() {
+                1
+            } else if media_range == "*/*" {
+                0
+            } else {
+                return None;
+            };
+            let quality = parts
+                .filter_map(|param| param.split_once('='))
+                .find(|(name, _)| name.trim().eq_ignore_ascii_case("q"))
+                .and_then(|(_, value)| value.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            Some((specificity, quality))
+        })
+        .max_by_key(|(specificity, _)| *specificity)
+        .is_some_and(|(_, quality)| quality > 0.0)
+}
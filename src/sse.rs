@@ -0,0 +1,260 @@
+//! Parsing of Open Responses SSE streams from sources other than the HTTP
+//! client (message queues, WebSocket relays, log files, ...) and encoding of
+//! streams for servers and proxies.
+//!
+//! Events go through the same parser and validator as
+//! [`StreamingClient::stream_response`](crate::StreamingClient::stream_response).
+
+use bytes::Bytes;
+use eventsource_stream::Eventsource;
+use futures::{Stream, StreamExt, stream};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::io::AsyncRead;
+use tokio_util::io::ReaderStream;
+
+use crate::streaming::{ResponseStream, StreamValidator, StreamingError};
+use crate::types::StreamingEvent;
+use crate::validation::{EventParser, ProtocolViolation, ValidationMode, ValidationPolicy};
+
+/// Parses a stream of SSE bytes with strict validation.
+pub fn parse_bytes<S, B, E>(stream: S) -> ResponseStream
+where
+    S: Stream<Item = Result<B, E>> + Send + 'static,
+    B: AsRef<[u8]> + Send + 'static,
+    E: fmt::Display + Send + 'static,
+{
+    SseParser::new().parse_bytes(stream)
+}
+
+/// Parses SSE read from `reader` with strict validation.
+pub fn parse_reader<R>(reader: R) -> ResponseStream
+where
+    R: AsyncRead + Send + 'static,
+{
+    SseParser::new().parse_reader(reader)
+}
+
+/// Parses a complete SSE transcript with strict validation.
+pub fn parse_str(text: &str) -> ResponseStream {
+    SseParser::new().parse_str(text)
+}
+
+/// Encodes `events` as SSE frames with [`SseEncoder`], ending with `[DONE]`.
+pub fn encode_stream<S>(events: S) -> EncodedStream<S>
+where
+    S: Stream<Item = StreamingEvent> + Unpin,
+{
+    SseEncoder::new().into_stream(events)
+}
+
+/// Configurable version of [`parse_bytes`], [`parse_reader`] and [`parse_str`].
+#[derive(Clone, Default)]
+pub struct SseParser {
+    policy: ValidationPolicy,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn validation_mode(mut self, mode: ValidationMode) -> Self {
+        self.policy.mode = mode;
+        self
+    }
+
+    /// Called for every violation recorded in [`ValidationMode::Warn`].
+    pub fn on_violation<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&ProtocolViolation) + Send + Sync + 'static,
+    {
+        self.policy.on_violation = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn parse_bytes<S, B, E>(&self, stream: S) -> ResponseStream
+    where
+        S: Stream<Item = Result<B, E>> + Send + 'static,
+        B: AsRef<[u8]> + Send + 'static,
+        E: fmt::Display + Send + 'static,
+    {
+        let mut parser = EventParser::new(self.policy.clone());
+        let report = parser.report();
+
+        let events = Box::pin(stream).eventsource().map(move |event| match event {
+            Ok(event) => parser.parse(event),
+            Err(error) => Err(StreamingError::StreamError(error.to_string())),
+        });
+
+        ResponseStream::new(events, report)
+    }
+
+    pub fn parse_reader<R>(&self, reader: R) -> ResponseStream
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        self.parse_bytes(ReaderStream::new(reader))
+    }
+
+    pub fn parse_str(&self, text: &str) -> ResponseStream {
+        let bytes = Bytes::copy_from_slice(text.as_bytes());
+        self.parse_bytes(stream::iter([Ok::<_, std::convert::Infallible>(bytes)]))
+    }
+}
+
+/// Writes `event: <type>` / `data: <json>` frames for a single response stream.
+///
+/// Sequence numbers are assigned by the encoder, overwriting whatever the
+/// events carry, and the outgoing sequence is checked by the same validator
+/// used for incoming streams. The first error finishes the encoder, like
+/// `[DONE]` does, so a rejected event is never followed by more frames.
+pub struct SseEncoder {
+    next_sequence_number: i32,
+    validator: StreamValidator,
+    finished: bool,
+    failed: bool,
+    /// Set once an event carried `i32::MAX`, leaving no number for the next one.
+    exhausted: bool,
+}
+
+impl Default for SseEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SseEncoder {
+    pub fn new() -> Self {
+        Self {
+            next_sequence_number: 0,
+            validator: StreamValidator::default(),
+            finished: false,
+            failed: false,
+            exhausted: false,
+        }
+    }
+
+    /// Sequence number for the first event, e.g. `starting_after + 1` when
+    /// resuming a stream.
+    pub fn starting_at(mut self, sequence_number: i32) -> Self {
+        self.next_sequence_number = sequence_number;
+        self
+    }
+
+    /// Encodes one event. [`StreamingEvent::Done`] produces the `[DONE]`
+    /// marker, after which no further events are accepted. Neither are they
+    /// after an error.
+    pub fn encode(&mut self, mut event: StreamingEvent) -> Result<Bytes, StreamingError> {
+        if self.finished {
+            return Err(self.finished_error(&event));
+        }
+
+        if matches!(event, StreamingEvent::Done) {
+            return self.done();
+        }
+
+        let frame = if self.exhausted {
+            Err(StreamingError::LifecycleError {
+                message: format!(
+                    "`{}` has no sequence number left after {}",
+                    event.event_type(),
+                    i32::MAX
+                ),
+            })
+        } else {
+            event.set_sequence_number(self.next_sequence_number);
+            self.frame(&event)
+        };
+        if frame.is_err() {
+            self.failed = true;
+            self.finished = true;
+        }
+        frame
+    }
+
+    /// Encodes the `[DONE]` marker.
+    pub fn done(&mut self) -> Result<Bytes, StreamingError> {
+        if self.finished {
+            return Err(self.finished_error(&StreamingEvent::Done));
+        }
+
+        let observed = self.validator.observe(&StreamingEvent::Done);
+        self.finished = true;
+        if observed.is_err() {
+            self.failed = true;
+        }
+        observed.map(|_| Bytes::from_static(b"data: [DONE]\n\n"))
+    }
+
+    /// Whether `[DONE]` was encoded or an error ended the stream.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn frame(&mut self, event: &StreamingEvent) -> Result<Bytes, StreamingError> {
+        self.validator.observe(event)?;
+        let data = serde_json::to_string(event)?;
+        match self.next_sequence_number.checked_add(1) {
+            Some(next) => self.next_sequence_number = next,
+            None => self.exhausted = true,
+        }
+
+        Ok(Bytes::from(format!(
+            "event: {}\ndata: {data}\n\n",
+            event.event_type()
+        )))
+    }
+
+    fn finished_error(&self, event: &StreamingEvent) -> StreamingError {
+        let after = if self.failed { "an earlier error" } else { "`[DONE]`" };
+        StreamingError::LifecycleError {
+            message: format!("`{}` was encoded after {after}", event.event_type()),
+        }
+    }
+
+    /// Sequence number the next event will receive.
+    pub fn next_sequence_number(&self) -> i32 {
+        self.next_sequence_number
+    }
+
+    pub fn into_stream<S>(self, events: S) -> EncodedStream<S>
+    where
+        S: Stream<Item = StreamingEvent> + Unpin,
+    {
+        EncodedStream {
+            inner: events,
+            encoder: self,
+        }
+    }
+}
+
+/// Stream returned by [`SseEncoder::into_stream`]. Appends `[DONE]` when the
+/// events end without it and stops after the first error.
+pub struct EncodedStream<S> {
+    inner: S,
+    encoder: SseEncoder,
+}
+
+impl<S> Stream for EncodedStream<S>
+where
+    S: Stream<Item = StreamingEvent> + Unpin,
+{
+    type Item = Result<Bytes, StreamingError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.encoder.is_finished() {
+            return Poll::Ready(None);
+        }
+
+        let frame = match this.inner.poll_next_unpin(cx) {
+            Poll::Ready(Some(event)) => this.encoder.encode(event),
+            Poll::Ready(None) => this.encoder.done(),
+            Poll::Pending => return Poll::Pending,
+        };
+        Poll::Ready(Some(frame))
+    }
+}
@@ -0,0 +1,90 @@
+use futures::{Stream, StreamExt};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::accumulator::StreamAccumulator;
+use crate::streaming::StreamingError;
+use crate::text_stream::{TextChunks, TextStreamOptions};
+use crate::types::{ResponseResource, StreamingEvent};
+
+/// Adapters for streams returned by [`StreamingClient`](crate::StreamingClient).
+pub trait ResponseStreamExt: Stream<Item = Result<StreamingEvent, StreamingError>> + Sized {
+    /// Pairs every event with a snapshot of the response assembled so far.
+    fn accumulate(self) -> Accumulate<Self> {
+        Accumulate {
+            inner: self,
+            accumulator: StreamAccumulator::new(),
+            failed: false,
+        }
+    }
+
+    /// Yields only text, refusal, reasoning summary and tool call chunks plus
+    /// the terminal response. Lifecycle and extension events are dropped.
+    fn text_chunks(self) -> TextChunks<Self> {
+        self.text_chunks_with(TextStreamOptions::default())
+    }
+
+    fn text_chunks_with(self, options: TextStreamOptions) -> TextChunks<Self> {
+        TextChunks::new(self, options)
+    }
+}
+
+impl<S> ResponseStreamExt for S where S: Stream<Item = Result<StreamingEvent, StreamingError>> {}
+
+#[derive(Debug, Clone)]
+pub struct AccumulatedEvent {
+    pub event: StreamingEvent,
+    /// `None` until `response.created` has been received. Shared with the
+    /// accumulator, so dropping it before polling the next event avoids a copy.
+    pub snapshot: Option<Arc<ResponseResource>>,
+}
+
+/// Stream returned by [`ResponseStreamExt::accumulate`]. Ends after the first
+/// accumulation error.
+pub struct Accumulate<S> {
+    inner: S,
+    accumulator: StreamAccumulator,
+    failed: bool,
+}
+
+impl<S> Accumulate<S> {
+    pub fn accumulator(&self) -> &StreamAccumulator {
+        &self.accumulator
+    }
+
+    pub fn into_accumulator(self) -> StreamAccumulator {
+        self.accumulator
+    }
+}
+
+impl<S> Stream for Accumulate<S>
+where
+    S: Stream<Item = Result<StreamingEvent, StreamingError>> + Unpin,
+{
+    type Item = Result<AccumulatedEvent, StreamingError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.failed {
+            return Poll::Ready(None);
+        }
+
+        let event = match this.inner.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(event))) => event,
+            Poll::Ready(Some(Err(error))) => return Poll::Ready(Some(Err(error))),
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => return Poll::Pending,
+        };
+
+        if let Err(error) = this.accumulator.apply(&event) {
+            this.failed = true;
+            return Poll::Ready(Some(Err(error.into())));
+        }
+
+        Poll::Ready(Some(Ok(AccumulatedEvent {
+            event,
+            snapshot: this.accumulator.shared_snapshot(),
+        })))
+    }
+}
@@ -1,18 +1,24 @@
-use eventsource_stream::{Event, Eventsource};
+use eventsource_stream::{Event, EventStreamError};
 use futures::{Stream, StreamExt};
-use reqwest::{
-    header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue},
-    Client as ReqwestClient,
-};
-use serde_json::Value;
+use reqwest::Proxy;
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
 use thiserror::Error;
-
+use tokio::time::Sleep;
+
+use crate::accumulator::AccumulatorError;
+use crate::auth::{AuthError, AuthProvider};
+use crate::metadata::ResponseMetadata;
+use crate::options::RequestOptions;
+use crate::responses_client::{BuildError, ResponsesClient, ResponsesClientBuilder};
+use crate::retry::{FailedResponse, RetryPolicy};
 use crate::types::{
     ApiErrorResponse, CreateResponseBody, Item, MessageStatus, StreamingEvent,
 };
-
-const DEFAULT_BASE_URL: &str = "https://api.openai.com";
+use crate::validation::{ProtocolViolation, ValidationMode, ViolationReport};
 
 #[derive(Error, Debug)]
 pub enum StreamingError {
@@ -28,8 +34,10 @@ pub enum StreamingError {
     #[error("API error ({status_code}): {raw_body}")]
     ApiError {
         status_code: u16,
-        error: Option<crate::types::ApiErrorDetail>,
+        error: Option<Box<crate::types::ApiErrorDetail>>,
         raw_body: String,
+        /// `x-request-id` of the failed request, when the server sent one.
+        request_id: Option<String>,
     },
 
     #[error("SSE event name `{sse_event}` does not match payload type `{body_type}`")]
@@ -40,6 +48,36 @@ pub enum StreamingError {
 
     #[error("Invalid event lifecycle: {message}")]
     LifecycleError { message: String },
+
+    #[error("{}", sequence_message(*previous, *received))]
+    SequenceError { previous: i32, received: i32 },
+
+    #[error("No streaming event received within {timeout:?}")]
+    IdleTimeout { timeout: Duration },
+
+    #[error("Invalid header value: {0}")]
+    InvalidHeader(String),
+
+    #[error("Authentication failed: {0}")]
+    Auth(#[from] AuthError),
+
+    #[error("Failed to accumulate stream: {0}")]
+    Accumulator(#[from] AccumulatorError),
+}
+
+fn sequence_message(previous: i32, received: i32) -> String {
+    if received == previous {
+        format!("Duplicate sequence number {received}")
+    } else if received < previous {
+        format!("Sequence number {received} arrived after {previous}")
+    } else {
+        match previous.checked_add(1) {
+            Some(next) => format!(
+                "Sequence numbers {next}..{received} were skipped after {previous}"
+            ),
+            None => format!("Sequence number {received} arrived after {previous}"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -48,60 +86,130 @@ pub struct RawSseEvent {
     pub data: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct ResumeOptions {
+    pub max_reconnects: u32,
+    pub reconnect_delay: Duration,
+}
+
+impl Default for ResumeOptions {
+    fn default() -> Self {
+        Self {
+            max_reconnects: 3,
+            reconnect_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl ResumeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_reconnects(mut self, max_reconnects: u32) -> Self {
+        self.max_reconnects = max_reconnects;
+        self
+    }
+
+    pub fn reconnect_delay(mut self, delay: Duration) -> Self {
+        self.reconnect_delay = delay;
+        self
+    }
+}
+
+pub(crate) type SseEventStream =
+    Pin<Box<dyn Stream<Item = Result<Event, EventStreamError<reqwest::Error>>> + Send>>;
+
 pub struct StreamingClientBuilder {
-    api_key: String,
-    base_url: Option<String>,
+    inner: ResponsesClientBuilder,
 }
 
 impl StreamingClientBuilder {
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
-            api_key: api_key.into(),
-            base_url: None,
+            inner: ResponsesClientBuilder::new(api_key),
         }
     }
 
     pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
-        self.base_url = Some(base_url.into());
+        self.inner = self.inner.base_url(base_url);
         self
     }
 
-    pub fn build(self) -> StreamingClient {
-        let mut base_url = self.base_url.unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+    /// Retries apply to establishing the stream only: once the first event has
+    /// been delivered, failures are reported to the caller as they happen.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.inner = self.inner.retry_policy(retry_policy);
+        self
+    }
 
-        if base_url.ends_with('/') {
-            base_url.pop();
-        }
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.connect_timeout(timeout);
+        self
+    }
 
-        if !base_url.ends_with("/v1") {
-            base_url.push_str("/v1");
-        }
+    /// Fails the stream with `StreamingError::IdleTimeout` when no event arrives
+    /// within `timeout`. Resumable streams reconnect instead when they can.
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.idle_timeout(timeout);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.inner = self.inner.proxy(proxy);
+        self
+    }
 
-        let mut headers = HeaderMap::new();
-        headers.insert(
-            CONTENT_TYPE,
-            HeaderValue::from_static("application/json"),
-        );
-        headers.insert(ACCEPT, HeaderValue::from_static("text/event-stream"));
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.inner = self.inner.default_header(name, value);
+        self
+    }
 
-        let inner = ReqwestClient::builder()
-            .default_headers(headers)
-            .build()
-            .expect("Failed to create HTTP client");
+    pub fn organization(mut self, organization: impl Into<String>) -> Self {
+        self.inner = self.inner.organization(organization);
+        self
+    }
+
+    pub fn project(mut self, project: impl Into<String>) -> Self {
+        self.inner = self.inner.project(project);
+        self
+    }
+
+    pub fn auth(mut self, auth: impl AuthProvider + 'static) -> Self {
+        self.inner = self.inner.auth(auth);
+        self
+    }
+
+    pub fn validation_mode(mut self, mode: ValidationMode) -> Self {
+        self.inner = self.inner.validation_mode(mode);
+        self
+    }
+
+    pub fn on_violation<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&ProtocolViolation) + Send + Sync + 'static,
+    {
+        self.inner = self.inner.on_violation(callback);
+        self
+    }
 
+    pub fn build(self) -> StreamingClient {
         StreamingClient {
-            inner,
-            base_url,
-            api_key: self.api_key,
+            inner: self.inner.build(),
         }
     }
+
+    pub fn try_build(self) -> Result<StreamingClient, BuildError> {
+        Ok(StreamingClient {
+            inner: self.inner.try_build()?,
+        })
+    }
 }
 
+/// Streaming view of a [`ResponsesClient`].
 #[derive(Clone)]
 pub struct StreamingClient {
-    inner: ReqwestClient,
-    base_url: String,
-    api_key: String,
+    pub(crate) inner: ResponsesClient,
 }
 
 impl StreamingClient {
@@ -121,131 +229,190 @@ impl StreamingClient {
 
     pub async fn stream_response(
         &self,
-        mut request: CreateResponseBody,
-    ) -> Result<impl Stream<Item = Result<StreamingEvent, StreamingError>>, StreamingError> {
-        request.stream = Some(true);
-
-        let url = format!("{}/responses", self.base_url);
-
-        let response = self
-            .inner
-            .post(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.api_key))
-            .json(&request)
-            .send()
-            .await?;
-
-        let status = response.status();
-
-        if !status.is_success() {
-            let error_text = response.text().await?;
-            return Err(StreamingError::ApiError {
-                status_code: status.as_u16(),
-                error: ApiErrorResponse::parse(&error_text),
-                raw_body: error_text,
-            });
-        }
+        request: CreateResponseBody,
+    ) -> Result<ResponseStream, StreamingError> {
+        self.inner.stream_response(request).await
+    }
 
-        let stream = response.bytes_stream();
-        let eventsource = stream.eventsource();
-        let mut validator = StreamValidator::default();
+    pub async fn stream_response_with_options(
+        &self,
+        request: CreateResponseBody,
+        options: RequestOptions,
+    ) -> Result<ResponseStream, StreamingError> {
+        self.inner.stream_response_with_options(request, options).await
+    }
 
-        let event_stream = eventsource.map(move |event| match event {
-            Ok(event) => parse_streaming_event(event, &mut validator),
-            Err(error) => Err(StreamingError::StreamError(error.to_string())),
-        });
+    /// See [`ResponsesClient::stream_response_resumable`].
+    pub async fn stream_response_resumable(
+        &self,
+        request: CreateResponseBody,
+        options: ResumeOptions,
+    ) -> Result<ResponseStream, StreamingError> {
+        self.inner.stream_response_resumable(request, options).await
+    }
 
-        Ok(event_stream)
+    /// See [`ResponsesClient::stream_response_resumable_with_options`].
+    pub async fn stream_response_resumable_with_options(
+        &self,
+        request: CreateResponseBody,
+        options: ResumeOptions,
+        request_options: RequestOptions,
+    ) -> Result<ResponseStream, StreamingError> {
+        self.inner
+            .stream_response_resumable_with_options(request, options, request_options)
+            .await
     }
 
     pub async fn stream_response_lines(
         &self,
-        mut request: CreateResponseBody,
-    ) -> Result<impl Stream<Item = Result<RawSseEvent, StreamingError>>, StreamingError> {
-        request.stream = Some(true);
-
-        let url = format!("{}/responses", self.base_url);
-
-        let response = self
-            .inner
-            .post(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.api_key))
-            .json(&request)
-            .send()
-            .await?;
-
-        let status = response.status();
-
-        if !status.is_success() {
-            let error_text = response.text().await?;
-            return Err(StreamingError::ApiError {
-                status_code: status.as_u16(),
-                error: ApiErrorResponse::parse(&error_text),
-                raw_body: error_text,
-            });
-        }
+        request: CreateResponseBody,
+    ) -> Result<impl Stream<Item = Result<RawSseEvent, StreamingError>> + use<>, StreamingError> {
+        self.inner.stream_response_lines(request).await
+    }
 
-        let stream = response.bytes_stream();
-        let eventsource = stream.eventsource();
-
-        let line_stream = eventsource.map(|event| match event {
-            Ok(event) => {
-                let event_name = if event.data == "[DONE]" || event.event.is_empty() {
-                    None
-                } else {
-                    Some(event.event)
-                };
-
-                Ok(RawSseEvent {
-                    event: event_name,
-                    data: event.data,
-                })
-            }
-            Err(error) => Err(StreamingError::StreamError(error.to_string())),
-        });
+    pub fn responses_client(&self) -> &ResponsesClient {
+        &self.inner
+    }
+}
 
-        Ok(line_stream)
+impl From<ResponsesClient> for StreamingClient {
+    fn from(inner: ResponsesClient) -> Self {
+        Self { inner }
     }
 }
 
-fn parse_streaming_event(
-    event: Event,
-    validator: &mut StreamValidator,
-) -> Result<StreamingEvent, StreamingError> {
-    if event.data == "[DONE]" {
-        validator.observe(&StreamingEvent::Done)?;
-        return Ok(StreamingEvent::Done);
+impl From<StreamingClient> for ResponsesClient {
+    fn from(client: StreamingClient) -> Self {
+        client.inner
+    }
+}
+
+/// Stream of parsed events returned by the streaming methods.
+pub struct ResponseStream {
+    inner: Pin<Box<dyn Stream<Item = Result<StreamingEvent, StreamingError>> + Send>>,
+    report: ViolationReport,
+    last_sequence_number: Option<i32>,
+    metadata: Option<Box<ResponseMetadata>>,
+}
+
+impl ResponseStream {
+    pub(crate) fn new<S>(inner: S, report: ViolationReport) -> Self
+    where
+        S: Stream<Item = Result<StreamingEvent, StreamingError>> + Send + 'static,
+    {
+        Self {
+            inner: Box::pin(inner),
+            report,
+            last_sequence_number: None,
+            metadata: None,
+        }
+    }
+
+    pub(crate) fn with_metadata(mut self, metadata: ResponseMetadata) -> Self {
+        self.metadata = Some(Box::new(metadata));
+        self
+    }
+
+    /// HTTP metadata of the request that opened the stream. `None` for
+    /// streams that were not read from an HTTP response, such as replays.
+    pub fn metadata(&self) -> Option<&ResponseMetadata> {
+        self.metadata.as_deref()
     }
 
-    let raw_value: Value = serde_json::from_str(&event.data)?;
-    let body_type = raw_value
-        .get("type")
-        .and_then(Value::as_str)
-        .ok_or_else(|| StreamingError::StreamError("Streaming payload is missing `type`".to_string()))?
-        .to_string();
+    /// Violations recorded so far in [`ValidationMode::Warn`]. Take the handle
+    /// before passing the stream to an adapter to read it once the stream ends.
+    pub fn report(&self) -> ViolationReport {
+        self.report.clone()
+    }
 
-    if event.event.is_empty() {
-        return Err(StreamingError::MissingEventType { body_type });
+    pub fn violations(&self) -> Vec<ProtocolViolation> {
+        self.report.violations()
     }
 
-    if event.event != body_type {
-        return Err(StreamingError::EventTypeMismatch {
-            sse_event: event.event,
-            body_type,
-        });
+    /// Sequence number of the last event yielded, usable as `starting_after`
+    /// when resuming the response later.
+    pub fn last_sequence_number(&self) -> Option<i32> {
+        self.last_sequence_number
     }
+}
 
-    let parsed = serde_json::from_value::<StreamingEvent>(raw_value)?;
-    validator.observe(&parsed)?;
-    Ok(parsed)
+impl Stream for ResponseStream {
+    type Item = Result<StreamingEvent, StreamingError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = self.inner.as_mut().poll_next(cx);
+        if let Poll::Ready(Some(Ok(event))) = &item {
+            if let Some(sequence_number) = event.sequence_number() {
+                self.last_sequence_number = Some(sequence_number);
+            }
+        }
+        item
+    }
+}
+
+pub(crate) fn with_idle_timeout<S>(stream: S, timeout: Option<Duration>) -> IdleTimeout<S> {
+    IdleTimeout {
+        inner: stream,
+        timeout,
+        sleep: None,
+        timed_out: false,
+    }
+}
+
+pub(crate) struct IdleTimeout<S> {
+    inner: S,
+    timeout: Option<Duration>,
+    sleep: Option<Pin<Box<Sleep>>>,
+    timed_out: bool,
+}
+
+impl<S, T> Stream for IdleTimeout<S>
+where
+    S: Stream<Item = Result<T, StreamingError>> + Unpin,
+{
+    type Item = Result<T, StreamingError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.timed_out {
+            return Poll::Ready(None);
+        }
+
+        if let Poll::Ready(item) = this.inner.poll_next_unpin(cx) {
+            this.sleep = None;
+            return Poll::Ready(item);
+        }
+
+        if let Some(timeout) = this.timeout {
+            let sleep = this
+                .sleep
+                .get_or_insert_with(|| Box::pin(tokio::time::sleep(timeout)));
+            if sleep.as_mut().poll(cx).is_ready() {
+                this.timed_out = true;
+                return Poll::Ready(Some(Err(StreamingError::IdleTimeout { timeout })));
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+pub(crate) fn api_error(failed: FailedResponse) -> StreamingError {
+    StreamingError::ApiError {
+        status_code: failed.status,
+        error: ApiErrorResponse::parse(&failed.body).map(Box::new),
+        raw_body: failed.body,
+        request_id: failed.request_id,
+    }
 }
 
 #[derive(Default)]
-struct StreamValidator {
+pub(crate) struct StreamValidator {
     outputs: HashMap<i32, OutputItemState>,
     item_to_output: HashMap<String, i32>,
     terminal_incomplete_output: Option<i32>,
     saw_stream_error: bool,
+    last_sequence_number: Option<i32>,
 }
 
 #[derive(Default)]
@@ -260,7 +427,16 @@ struct ContentPartState {
 }
 
 impl StreamValidator {
-    fn observe(&mut self, event: &StreamingEvent) -> Result<(), StreamingError> {
+    /// Checks `event` against the sequence and lifecycle rules. Both are
+    /// always applied so a sequence gap doesn't leave the lifecycle state
+    /// behind; the sequence error wins when both fail.
+    pub(crate) fn observe(&mut self, event: &StreamingEvent) -> Result<(), StreamingError> {
+        let sequence = self.observe_sequence(event);
+        let lifecycle = self.observe_lifecycle(event);
+        sequence.and(lifecycle)
+    }
+
+    fn observe_lifecycle(&mut self, event: &StreamingEvent) -> Result<(), StreamingError> {
         if let Some(terminal_output) = self.terminal_incomplete_output {
             if let Some(output_index) = output_index_for_event(event) {
                 if output_index > terminal_output {
@@ -436,6 +612,24 @@ impl StreamValidator {
         Ok(())
     }
 
+    fn observe_sequence(&mut self, event: &StreamingEvent) -> Result<(), StreamingError> {
+        let Some(received) = event.sequence_number() else {
+            return Ok(());
+        };
+
+        let previous = self.last_sequence_number;
+        if previous.is_none_or(|previous| received > previous) {
+            self.last_sequence_number = Some(received);
+        }
+
+        match previous {
+            Some(previous) if previous.checked_add(1) != Some(received) => {
+                Err(StreamingError::SequenceError { previous, received })
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn register_item(&mut self, item: &Item, output_index: i32) -> Result<(), StreamingError> {
         if let Some(item_id) = item_id(item) {
             if let Some(existing) = self.item_to_output.get(item_id) {
@@ -576,13 +770,35 @@ mod tests {
     #[test]
     fn test_streaming_client_creation() {
         let client = StreamingClient::new("test-api-key");
-        assert_eq!(client.api_key, "test-api-key");
-        assert_eq!(client.base_url, "https://api.openai.com/v1");
+        assert_eq!(client.inner.api_key, "test-api-key");
+        assert_eq!(client.inner.base_url, "https://api.openai.com/v1");
+    }
+
+    #[test]
+    fn test_sequence_number_overflow_is_an_error() {
+        let event = |sequence_number: i32| -> StreamingEvent {
+            serde_json::from_value(serde_json::json!({
+                "type": "response.output_text.done",
+                "sequence_number": sequence_number,
+                "item_id": "msg_1",
+                "output_index": 0,
+                "content_index": 0,
+                "text": ""
+            }))
+            .unwrap()
+        };
+        let mut validator = StreamValidator::default();
+        let _ = validator.observe(&event(i32::MAX));
+
+        assert!(matches!(
+            validator.observe(&event(i32::MIN)),
+            Err(StreamingError::SequenceError { previous: i32::MAX, received: i32::MIN })
+        ));
     }
 
     #[test]
     fn test_streaming_client_with_base_url_normalization() {
         let client = StreamingClient::with_base_url("test-key", "https://openrouter.ai/api");
-        assert_eq!(client.base_url, "https://openrouter.ai/api/v1");
+        assert_eq!(client.inner.base_url, "https://openrouter.ai/api/v1");
     }
 }
@@ -0,0 +1,488 @@
+//! An in-process mock Open Responses server for tests.
+//!
+//! Requires the `testing` feature.
+//!
+//! ```rust,no_run
+//! use openresponses_rust::testing::{self, MockReply, MockServer};
+//! use openresponses_rust::{CreateResponseBody, Item, ResponsesClient};
+//!
+//! # async fn run() {
+//! let server = MockServer::start().await;
+//! server.enqueue(MockReply::response(testing::response(
+//!     "resp_1",
+//!     vec![Item::assistant_message("Hello!")],
+//! )));
+//!
+//! let client = ResponsesClient::with_base_url("test-key", server.url());
+//! let response = client.create_response(CreateResponseBody::default()).await.unwrap();
+//!
+//! assert_eq!(response.id, "resp_1");
+//! assert_eq!(server.requests().len(), 1);
+//! # }
+//! ```
+
+use axum::Router;
+use axum::body::{Body, Bytes};
+use axum::extract::State;
+use axum::http::header::CONNECTION;
+use axum::http::{HeaderValue, Method, Uri};
+use axum::response::{IntoResponse, Json, Response};
+use axum::routing::{get, post};
+use futures::{StreamExt, stream};
+use serde_json::{Value, json};
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, ready};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use crate::server::{ProviderError, event_stream_response};
+use crate::sse::SseEncoder;
+use crate::types::{
+    CreateResponseBody, FunctionCallStatus, Item, MessageContent, MessageStatus,
+    ResponseResource, StreamingEvent,
+};
+
+pub use crate::server::response_events;
+
+/// A completed response from `mock-model` with the given output.
+pub fn response(id: impl Into<String>, output: Vec<Item>) -> ResponseResource {
+    let mut response: ResponseResource = serde_json::from_value(json!({
+        "id": id.into(),
+        "object": "response",
+        "created_at": 1_700_000_000,
+        "completed_at": 1_700_000_001,
+        "status": "completed",
+        "model": "mock-model",
+        "output": [],
+        "tools": [],
+        "tool_choice": "auto",
+        "truncation": "disabled",
+        "parallel_tool_calls": true,
+        "text": {"format": {"type": "text"}},
+        "top_p": 1.0,
+        "presence_penalty": 0.0,
+        "frequency_penalty": 0.0,
+        "top_logprobs": 0,
+        "temperature": 1.0,
+        "store": true,
+        "background": false,
+        "service_tier": "default",
+        "metadata": {}
+    }))
+    .expect("mock response template is valid");
+    response.output = output;
+    response
+}
+
+pub fn function_call(
+    call_id: impl Into<String>,
+    name: impl Into<String>,
+    arguments: impl Into<String>,
+) -> Item {
+    Item::FunctionCall {
+        id: None,
+        call_id: call_id.into(),
+        name: name.into(),
+        arguments: arguments.into(),
+        status: Some(FunctionCallStatus::Completed),
+    }
+}
+
+pub fn reasoning(summary: impl Into<String>) -> Item {
+    Item::Reasoning {
+        id: None,
+        status: Some(MessageStatus::Completed),
+        content: None,
+        summary: vec![MessageContent::summary(summary)],
+        encrypted_content: None,
+    }
+}
+
+/// One scripted reply of a [`MockServer`].
+#[derive(Debug, Clone)]
+pub struct MockReply {
+    kind: ReplyKind,
+    disconnect_after: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+enum ReplyKind {
+    Response(Box<ResponseResource>),
+    Events(Vec<StreamingEvent>),
+    RawSse(String),
+    Json(Value),
+    Error(ProviderError),
+    RateLimited(Duration),
+}
+
+impl MockReply {
+    /// Answers with `response` as JSON, or with the events generated by
+    /// [`response_events`] when the request asks for a stream.
+    pub fn response(response: ResponseResource) -> Self {
+        Self::new(ReplyKind::Response(Box::new(response)))
+    }
+
+    /// Streams `events` as given. Sequence numbers and `[DONE]` are added,
+    /// and an event that breaks the streaming rules aborts the connection.
+    pub fn events(events: Vec<StreamingEvent>) -> Self {
+        Self::new(ReplyKind::Events(events))
+    }
+
+    /// Sends `body` verbatim as an SSE stream, for off-spec servers.
+    pub fn raw_sse(body: impl Into<String>) -> Self {
+        Self::new(ReplyKind::RawSse(body.into()))
+    }
+
+    /// The body of a successful `DELETE /v1/responses/{id}`.
+    pub fn deleted(id: impl Into<String>) -> Self {
+        Self::new(ReplyKind::Json(json!({
+            "id": id.into(),
+            "object": "response.deleted",
+            "deleted": true
+        })))
+    }
+
+    pub fn error(error: ProviderError) -> Self {
+        Self::new(ReplyKind::Error(error))
+    }
+
+    /// A 429 asking the client to wait `retry_after` through both
+    /// `Retry-After` and `retry-after-ms`.
+    pub fn rate_limited(retry_after: Duration) -> Self {
+        Self::new(ReplyKind::RateLimited(retry_after))
+    }
+
+    /// Drops the connection after `frames` SSE frames have been sent. Only
+    /// applies to streamed replies, and counts from `starting_after` when
+    /// the reply resumes a stream.
+    pub fn disconnect_after(mut self, frames: usize) -> Self {
+        self.disconnect_after = Some(frames);
+        self
+    }
+
+    fn new(kind: ReplyKind) -> Self {
+        Self {
+            kind,
+            disconnect_after: None,
+        }
+    }
+
+    fn into_response(self, streaming: bool, starting_after: Option<i32>) -> Response {
+        let frames = match self.kind {
+            ReplyKind::Response(response) if !streaming => return Json(*response).into_response(),
+            ReplyKind::Response(response) => {
+                resume(encode(response_events(&response)), starting_after)
+            }
+            ReplyKind::Events(events) => resume(encode(events), starting_after),
+            ReplyKind::RawSse(body) => body
+                .split_inclusive("\n\n")
+                .map(|frame| Ok(Bytes::from(frame.to_string())))
+                .collect(),
+            ReplyKind::Json(body) => return Json(body).into_response(),
+            ReplyKind::Error(error) => return error.into_response(),
+            ReplyKind::RateLimited(retry_after) => {
+                let mut response = ProviderError::too_many_requests("Rate limit exceeded")
+                    .code("rate_limit_exceeded")
+                    .into_response();
+                let headers = response.headers_mut();
+                headers.insert("retry-after", HeaderValue::from(retry_after.as_secs_f64().ceil() as u64));
+                headers.insert("retry-after-ms", HeaderValue::from(retry_after.as_millis() as u64));
+                return response;
+            }
+        };
+
+        let Some(limit) = self.disconnect_after else {
+            return event_stream_response(Body::from_stream(stream::iter(frames)));
+        };
+
+        let mut frames = frames;
+        frames.truncate(limit);
+        // The connection is closed once the marker has been written (see
+        // `MockConnection`); until then the body stays open.
+        let marker = stream::once(async { Ok(Bytes::from_static(DISCONNECT_MARKER)) });
+        let body = Body::from_stream(stream::iter(frames).chain(marker).chain(stream::pending()));
+
+        // Keeps the client from reusing the aborted connection.
+        let mut response = event_stream_response(body);
+        response
+            .headers_mut()
+            .insert(CONNECTION, HeaderValue::from_static("close"));
+        response
+    }
+}
+
+fn encode(events: Vec<StreamingEvent>) -> Vec<Result<Bytes, std::io::Error>> {
+    let mut encoder = SseEncoder::new();
+    let mut frames = Vec::new();
+    for event in events.into_iter().chain([StreamingEvent::Done]) {
+        match encoder.encode(event) {
+            Ok(frame) => frames.push(Ok(frame)),
+            Err(error) => {
+                frames.push(Err(std::io::Error::other(error.to_string())));
+                break;
+            }
+        }
+        if encoder.is_finished() {
+            break;
+        }
+    }
+    frames
+}
+
+/// Drops the frames a client resuming after `starting_after` has already
+/// seen. The encoder numbers events from 0, so frame `n` carries sequence
+/// number `n`.
+fn resume(
+    mut frames: Vec<Result<Bytes, std::io::Error>>,
+    starting_after: Option<i32>,
+) -> Vec<Result<Bytes, std::io::Error>> {
+    let seen = starting_after
+        .and_then(|sequence_number| usize::try_from(sequence_number).ok())
+        .map_or(0, |sequence_number| sequence_number + 1);
+    frames.drain(..seen.min(frames.len()));
+    frames
+}
+
+/// Ends a `disconnect_after` reply. It is an SSE comment, so clients ignore
+/// it even though it reaches them.
+const DISCONNECT_MARKER: &[u8] = b": mock-server-disconnect\n\n";
+
+struct MockListener(TcpListener);
+
+impl axum::serve::Listener for MockListener {
+    type Io = MockConnection;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        let (stream, address) = axum::serve::Listener::accept(&mut self.0).await;
+        let connection = MockConnection {
+            stream,
+            bytes_before_close: None,
+            closing: false,
+        };
+        (connection, address)
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        self.0.local_addr()
+    }
+}
+
+/// Shuts the socket down right after [`DISCONNECT_MARKER`] has been written,
+/// so every frame before it is guaranteed to have reached the client. Failing
+/// the response body instead would drop frames hyper has not flushed yet.
+struct MockConnection {
+    stream: TcpStream,
+    /// Bytes left to write up to the end of the marker.
+    bytes_before_close: Option<usize>,
+    closing: bool,
+}
+
+impl AsyncRead for MockConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for MockConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.closing {
+            return Poll::Ready(Err(disconnected()));
+        }
+
+        let limit = self.bytes_before_close.or_else(|| {
+            buf.windows(DISCONNECT_MARKER.len())
+                .position(|window| window == DISCONNECT_MARKER)
+                .map(|start| start + DISCONNECT_MARKER.len())
+        });
+        let Some(limit) = limit else {
+            return Pin::new(&mut self.stream).poll_write(cx, buf);
+        };
+
+        let written = ready!(Pin::new(&mut self.stream).poll_write(cx, &buf[..limit]))?;
+        let remaining = limit - written;
+        self.bytes_before_close = (remaining > 0).then_some(remaining);
+        self.closing = remaining == 0;
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(Pin::new(&mut self.stream).poll_flush(cx))?;
+        if self.closing {
+            ready!(Pin::new(&mut self.stream).poll_shutdown(cx))?;
+            return Poll::Ready(Err(disconnected()));
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
+
+fn disconnected() -> io::Error {
+    io::Error::new(io::ErrorKind::ConnectionAborted, "mock server disconnected")
+}
+
+#[derive(Default)]
+struct MockState {
+    replies: VecDeque<MockReply>,
+    requests: Vec<CreateResponseBody>,
+    calls: Vec<String>,
+}
+
+/// Serves the Open Responses endpoints on a local port, answering with the
+/// enqueued [`MockReply`]s in order:
+///
+/// - `POST /v1/responses`, recording every request body;
+/// - `GET /v1/responses/{id}`, streamed with `stream=true` and resumed after
+///   `starting_after` like a real server;
+/// - `POST /v1/responses/{id}/cancel` and `DELETE /v1/responses/{id}`.
+///
+/// All routes share one queue and ignore the response id, so enqueue replies
+/// in the order the client will call them. Requests that arrive with an
+/// empty queue get a 500. The server stops when the `MockServer` is dropped.
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind mock server");
+        let address = listener.local_addr().expect("mock server has no address");
+        let state = Arc::new(Mutex::new(MockState::default()));
+
+        let app = Router::new()
+            .route("/v1/responses", post(create_response))
+            .route("/v1/responses/{id}", get(get_response).delete(other_call))
+            .route("/v1/responses/{id}/cancel", post(other_call))
+            .with_state(state.clone());
+        let task = tokio::spawn(async move {
+            let _ = axum::serve(MockListener(listener), app).await;
+        });
+
+        Self {
+            address,
+            state,
+            task,
+        }
+    }
+
+    /// Base URL for [`ResponsesClient::with_base_url`](crate::ResponsesClient::with_base_url).
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    pub fn enqueue(&self, reply: MockReply) -> &Self {
+        self.state().replies.push_back(reply);
+        self
+    }
+
+    /// Request bodies received so far, oldest first.
+    pub fn requests(&self) -> Vec<CreateResponseBody> {
+        self.state().requests.clone()
+    }
+
+    /// Every call received so far as `METHOD /path?query`, oldest first.
+    pub fn calls(&self) -> Vec<String> {
+        self.state().calls.clone()
+    }
+
+    pub fn pending_replies(&self) -> usize {
+        self.state().replies.len()
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn create_response(
+    State(state): State<Arc<Mutex<MockState>>>,
+    uri: Uri,
+    body: Bytes,
+) -> Response {
+    let request: CreateResponseBody = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(error) => {
+            return ProviderError::invalid_request(format!("Invalid request body: {error}"))
+                .into_response();
+        }
+    };
+    let streaming = request.stream == Some(true);
+
+    match next_reply(&state, format!("POST {uri}"), Some(request)) {
+        Some(reply) => reply.into_response(streaming, None),
+        None => no_reply(),
+    }
+}
+
+async fn get_response(State(state): State<Arc<Mutex<MockState>>>, uri: Uri) -> Response {
+    let mut streaming = false;
+    let mut starting_after = None;
+    let query = uri.query().unwrap_or_default();
+    for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+        match key.as_ref() {
+            "stream" => streaming = value == "true",
+            "starting_after" => starting_after = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    match next_reply(&state, format!("GET {uri}"), None) {
+        Some(reply) => reply.into_response(streaming, starting_after),
+        None => no_reply(),
+    }
+}
+
+/// Cancel and delete, which are always answered with JSON.
+async fn other_call(
+    State(state): State<Arc<Mutex<MockState>>>,
+    method: Method,
+    uri: Uri,
+) -> Response {
+    match next_reply(&state, format!("{method} {uri}"), None) {
+        Some(reply) => reply.into_response(false, None),
+        None => no_reply(),
+    }
+}
+
+fn next_reply(
+    state: &Mutex<MockState>,
+    call: String,
+    request: Option<CreateResponseBody>,
+) -> Option<MockReply> {
+    let mut state = state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    state.calls.push(call);
+    state.requests.extend(request);
+    state.replies.pop_front()
+}
+
+fn no_reply() -> Response {
+    ProviderError::server_error("No mock reply was enqueued").into_response()
+}
+
@@ -0,0 +1,200 @@
+use futures::{Stream, StreamExt};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::streaming::StreamingError;
+use crate::types::{ErrorPayload, Item, ResponseResource, StreamingEvent};
+
+/// A simplified view of a streaming event, produced by
+/// [`ResponseStreamExt::text_chunks`](crate::ResponseStreamExt::text_chunks).
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextChunk {
+    TextDelta {
+        output_index: i32,
+        content_index: i32,
+        text: String,
+    },
+    RefusalDelta {
+        output_index: i32,
+        content_index: i32,
+        text: String,
+    },
+    ReasoningSummaryDelta {
+        output_index: i32,
+        summary_index: i32,
+        text: String,
+    },
+    ToolCallStarted {
+        output_index: i32,
+        call_id: String,
+        name: String,
+    },
+    ToolCallArgumentsDelta {
+        output_index: i32,
+        call_id: String,
+        delta: String,
+    },
+    ToolCallFinished {
+        output_index: i32,
+        call_id: String,
+        name: String,
+        arguments: String,
+    },
+    /// An `error` event sent by the server.
+    Error(ErrorPayload),
+    Completed(ResponseResource),
+    Failed(ResponseResource),
+    Incomplete(ResponseResource),
+    /// Any other event, only emitted when enabled in [`TextStreamOptions`].
+    Event(StreamingEvent),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextStreamOptions {
+    pub include_lifecycle: bool,
+    pub include_unknown: bool,
+}
+
+impl TextStreamOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emits events without a dedicated chunk (`response.created`,
+    /// `response.output_item.added`, `[DONE]`, ...) as [`TextChunk::Event`].
+    pub fn include_lifecycle(mut self, include: bool) -> Self {
+        self.include_lifecycle = include;
+        self
+    }
+
+    /// Emits provider extension events (`StreamingEvent::Unknown`) as [`TextChunk::Event`].
+    pub fn include_unknown(mut self, include: bool) -> Self {
+        self.include_unknown = include;
+        self
+    }
+}
+
+pub struct TextChunks<S> {
+    inner: S,
+    options: TextStreamOptions,
+    call_ids: HashMap<String, String>,
+}
+
+impl<S> TextChunks<S> {
+    pub(crate) fn new(inner: S, options: TextStreamOptions) -> Self {
+        Self {
+            inner,
+            options,
+            call_ids: HashMap::new(),
+        }
+    }
+
+    fn chunk_for(&mut self, event: StreamingEvent) -> Option<TextChunk> {
+        let chunk = match event {
+            StreamingEvent::OutputTextDelta {
+                output_index,
+                content_index,
+                delta,
+                ..
+            } => TextChunk::TextDelta {
+                output_index,
+                content_index,
+                text: delta,
+            },
+            StreamingEvent::RefusalDelta {
+                output_index,
+                content_index,
+                delta,
+                ..
+            } => TextChunk::RefusalDelta {
+                output_index,
+                content_index,
+                text: delta,
+            },
+            StreamingEvent::ReasoningSummaryDelta {
+                output_index,
+                summary_index,
+                delta,
+                ..
+            } => TextChunk::ReasoningSummaryDelta {
+                output_index,
+                summary_index,
+                text: delta,
+            },
+            StreamingEvent::OutputItemAdded {
+                output_index,
+                item: Some(Item::FunctionCall { id, call_id, name, .. }),
+                ..
+            } => {
+                if let Some(id) = id {
+                    self.call_ids.insert(id, call_id.clone());
+                }
+                TextChunk::ToolCallStarted {
+                    output_index,
+                    call_id,
+                    name,
+                }
+            }
+            StreamingEvent::FunctionCallArgumentsDelta {
+                output_index,
+                item_id,
+                delta,
+                ..
+            } => TextChunk::ToolCallArgumentsDelta {
+                output_index,
+                call_id: self.call_ids.get(&item_id).cloned().unwrap_or(item_id),
+                delta,
+            },
+            StreamingEvent::OutputItemDone {
+                output_index,
+                item:
+                    Some(Item::FunctionCall {
+                        call_id,
+                        name,
+                        arguments,
+                        ..
+                    }),
+                ..
+            } => TextChunk::ToolCallFinished {
+                output_index,
+                call_id,
+                name,
+                arguments,
+            },
+            StreamingEvent::Error { error, .. } => TextChunk::Error(error),
+            StreamingEvent::ResponseCompleted { response, .. } => TextChunk::Completed(response),
+            StreamingEvent::ResponseFailed { response, .. } => TextChunk::Failed(response),
+            StreamingEvent::ResponseIncomplete { response, .. } => TextChunk::Incomplete(response),
+            StreamingEvent::Unknown { .. } if self.options.include_unknown => TextChunk::Event(event),
+            StreamingEvent::Unknown { .. } => return None,
+            _ if self.options.include_lifecycle => TextChunk::Event(event),
+            _ => return None,
+        };
+
+        Some(chunk)
+    }
+}
+
+impl<S> Stream for TextChunks<S>
+where
+    S: Stream<Item = Result<StreamingEvent, StreamingError>> + Unpin,
+{
+    type Item = Result<TextChunk, StreamingError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            let event = match this.inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(event))) => event,
+                Poll::Ready(Some(Err(error))) => return Poll::Ready(Some(Err(error))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            if let Some(chunk) = this.chunk_for(event) {
+                return Poll::Ready(Some(Ok(chunk)));
+            }
+        }
+    }
+}
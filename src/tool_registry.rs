@@ -0,0 +1,383 @@
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio_util::sync::CancellationToken;
+
+use crate::types::{FunctionCallOutputStatus, FunctionOutput, Item, Tool};
+
+#[derive(Error, Debug)]
+pub enum ToolError {
+    #[error("Unknown tool `{name}`")]
+    UnknownTool { name: String },
+
+    #[error("Invalid arguments for `{name}`: {source}")]
+    InvalidArguments {
+        name: String,
+        source: serde_json::Error,
+    },
+
+    #[error("Tool `{name}` failed: {message}")]
+    Failed { name: String, message: String },
+
+    #[error("Tool `{name}` timed out after {timeout:?}")]
+    TimedOut { name: String, timeout: Duration },
+
+    #[error("Tool `{name}` was cancelled")]
+    Cancelled { name: String },
+}
+
+impl ToolError {
+    /// The `error.type` reported to the model.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ToolError::UnknownTool { .. } => "unknown_tool",
+            ToolError::InvalidArguments { .. } => "invalid_arguments",
+            ToolError::Failed { .. } => "tool_error",
+            ToolError::TimedOut { .. } => "timeout",
+            ToolError::Cancelled { .. } => "cancelled",
+        }
+    }
+
+    /// The JSON sent back to the model in place of a result:
+    /// `{"error": {"type": ..., "message": ...}}`.
+    pub fn to_output(&self) -> String {
+        json!({"error": {"type": self.kind(), "message": self.to_string()}}).to_string()
+    }
+}
+
+/// Controls how [`ToolRegistry::execute_all_with`] runs a batch of calls.
+#[derive(Debug, Clone)]
+pub struct ExecuteOptions {
+    /// Run the calls concurrently. Defaults to `true`.
+    pub parallel: bool,
+    /// Upper bound on calls running at once when `parallel` is set.
+    pub max_concurrency: Option<usize>,
+    /// Per-call time limit.
+    pub timeout: Option<Duration>,
+    pub cancel_token: Option<CancellationToken>,
+}
+
+impl Default for ExecuteOptions {
+    fn default() -> Self {
+        Self {
+            parallel: true,
+            max_concurrency: None,
+            timeout: None,
+            cancel_token: None,
+        }
+    }
+}
+
+impl ExecuteOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn cancel_token(mut self, token: CancellationToken) -> Self {
+        self.cancel_token = Some(token);
+        self
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancel_token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    fn concurrency(&self, calls: usize) -> usize {
+        if self.parallel {
+            self.max_concurrency.unwrap_or(calls).max(1)
+        } else {
+            1
+        }
+    }
+}
+
+type ToolFuture = Pin<Box<dyn Future<Output = Result<String, ToolError>> + Send>>;
+type Handler = Arc<dyn Fn(Value) -> ToolFuture + Send + Sync>;
+
+#[derive(Clone)]
+struct RegisteredTool {
+    declaration: Tool,
+    handler: Handler,
+}
+
+/// Function tools with the async handlers that execute them.
+///
+/// Handlers take their arguments as any `DeserializeOwned` type and may
+/// return any `Serialize` value; strings are passed to the model verbatim and
+/// everything else as JSON. Failures never abort dispatch: unknown tools,
+/// arguments that do not deserialize and handler errors all become
+/// [`ToolError::to_output`] outputs the model can react to.
+///
+/// ```rust
+/// use openresponses_rust::{Tool, ToolRegistry};
+/// use serde::Deserialize;
+/// use serde_json::json;
+///
+/// #[derive(Deserialize)]
+/// struct Weather {
+///     city: String,
+/// }
+///
+/// let registry = ToolRegistry::new().register(
+///     Tool::function("get_weather").with_parameters(json!({
+///         "type": "object",
+///         "properties": {"city": {"type": "string"}},
+///         "required": ["city"]
+///     })),
+///     |args: Weather| async move { Ok::<_, String>(format!("Sunny in {}", args.city)) },
+/// );
+/// assert_eq!(registry.tools().len(), 1);
+/// ```
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    tools: HashMap<String, RegisteredTool>,
+    order: Vec<String>,
+}
+
+impl fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.order)
+            .finish()
+    }
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `handler` under the name of `tool`, replacing any earlier
+    /// registration with that name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tool` is not a [`Tool::Function`].
+    pub fn register<T, O, E, F, Fut>(mut self, tool: Tool, handler: F) -> Self
+    where
+        T: DeserializeOwned + Send + 'static,
+        O: Serialize + 'static,
+        E: fmt::Display + 'static,
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<O, E>> + Send + 'static,
+    {
+        let Tool::Function { name, .. } = &tool else {
+            panic!("only function tools can be registered, got {tool:?}");
+        };
+        let name = name.clone();
+
+        let handler_name = name.clone();
+        let erased: Handler = Arc::new(move |arguments: Value| {
+            let name = handler_name.clone();
+            let input = serde_json::from_value::<T>(arguments);
+            let call = input.map(&handler);
+            Box::pin(async move {
+                let output = call
+                    .map_err(|source| ToolError::InvalidArguments {
+                        name: name.clone(),
+                        source,
+                    })?
+                    .await
+                    .map_err(|error| ToolError::Failed {
+                        name: name.clone(),
+                        message: error.to_string(),
+                    })?;
+                output_text(&name, &output)
+            })
+        });
+
+        if !self.tools.contains_key(&name) {
+            self.order.push(name.clone());
+        }
+        self.tools.insert(
+            name,
+            RegisteredTool {
+                declaration: tool,
+                handler: erased,
+            },
+        );
+        self
+    }
+
+    /// Declarations for [`CreateResponseBody::tools`](crate::CreateResponseBody::tools),
+    /// in registration order.
+    pub fn tools(&self) -> Vec<Tool> {
+        self.order
+            .iter()
+            .map(|name| self.tools[name].declaration.clone())
+            .collect()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.tools.contains_key(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    /// Runs the handler for `name` with the raw JSON `arguments`. An empty
+    /// string is treated as `{}`.
+    pub async fn invoke(&self, name: &str, arguments: &str) -> Result<String, ToolError> {
+        let tool = self.tools.get(name).ok_or_else(|| ToolError::UnknownTool {
+            name: name.to_string(),
+        })?;
+        let arguments = if arguments.trim().is_empty() {
+            Ok(Value::Object(Map::new()))
+        } else {
+            serde_json::from_str(arguments)
+        };
+        let arguments = arguments.map_err(|source| ToolError::InvalidArguments {
+            name: name.to_string(),
+            source,
+        })?;
+        (tool.handler)(arguments).await
+    }
+
+    /// Executes an [`Item::FunctionCall`] and returns the matching
+    /// [`Item::FunctionCallOutput`], or `None` for any other item.
+    pub async fn execute(&self, item: &Item) -> Option<Item> {
+        self.execute_with(item, &ExecuteOptions::default()).await
+    }
+
+    /// Like [`execute`](Self::execute), applying the timeout and cancellation
+    /// token of `options`. Calls that time out or are cancelled produce an
+    /// `incomplete` output carrying the error.
+    pub async fn execute_with(&self, item: &Item, options: &ExecuteOptions) -> Option<Item> {
+        let Item::FunctionCall {
+            call_id,
+            name,
+            arguments,
+            ..
+        } = item
+        else {
+            return None;
+        };
+
+        let call = async {
+            match options.timeout {
+                Some(timeout) => tokio::time::timeout(timeout, self.invoke(name, arguments))
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(ToolError::TimedOut {
+                            name: name.clone(),
+                            timeout,
+                        })
+                    }),
+                None => self.invoke(name, arguments).await,
+            }
+        };
+        let result = match &options.cancel_token {
+            Some(token) => tokio::select! {
+                biased;
+                _ = token.cancelled() => Err(ToolError::Cancelled { name: name.clone() }),
+                result = call => result,
+            },
+            None => call.await,
+        };
+
+        let (output, status) = match result {
+            Ok(output) => (output, FunctionCallOutputStatus::Completed),
+            Err(error @ (ToolError::TimedOut { .. } | ToolError::Cancelled { .. })) => {
+                (error.to_output(), FunctionCallOutputStatus::Incomplete)
+            }
+            Err(error) => (error.to_output(), FunctionCallOutputStatus::Completed),
+        };
+        Some(Item::FunctionCallOutput {
+            id: None,
+            call_id: call_id.clone(),
+            output: FunctionOutput::Text(output),
+            status: Some(status),
+        })
+    }
+
+    /// Executes every function call in `items` in order, skipping other items.
+    pub async fn execute_all(&self, items: &[Item]) -> Vec<Item> {
+        let mut outputs = Vec::new();
+        for item in items {
+            if let Some(output) = self.execute(item).await {
+                outputs.push(output);
+            }
+        }
+        outputs
+    }
+
+    /// Executes every function call in `items` as configured by `options`,
+    /// skipping other items. Outputs are returned in call order no matter
+    /// which call finishes first.
+    pub async fn execute_all_with(&self, items: &[Item], options: &ExecuteOptions) -> Vec<Item> {
+        self.execute_ordered(items, options).collect().await
+    }
+
+    /// Lazily executes the function calls in `items`, yielding their outputs
+    /// in call order.
+    pub(crate) fn execute_ordered(
+        &self,
+        items: &[Item],
+        options: &ExecuteOptions,
+    ) -> BoxStream<'static, Item> {
+        let calls: Vec<Item> = items
+            .iter()
+            .filter(|item| matches!(item, Item::FunctionCall { .. }))
+            .cloned()
+            .collect();
+        let concurrency = options.concurrency(calls.len());
+        let registry = Arc::new(self.clone());
+        let options = options.clone();
+
+        stream::iter(calls)
+            .map(move |call| {
+                let registry = registry.clone();
+                let options = options.clone();
+                async move {
+                    registry
+                        .execute_with(&call, &options)
+                        .await
+                        .expect("only function calls are executed")
+                }
+            })
+            .buffered(concurrency)
+            .boxed()
+    }
+}
+
+fn output_text<O: Serialize>(name: &str, output: &O) -> Result<String, ToolError> {
+    match serde_json::to_value(output) {
+        Ok(Value::String(text)) => Ok(text),
+        Ok(value) => Ok(value.to_string()),
+        Err(error) => Err(ToolError::Failed {
+            name: name.to_string(),
+            message: format!("result could not be serialized: {error}"),
+        }),
+    }
+}
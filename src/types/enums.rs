@@ -34,20 +34,15 @@ pub enum FunctionCallOutputStatus {
     Incomplete,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum ImageDetail {
     Low,
     High,
+    #[default]
     Auto,
 }
 
-impl Default for ImageDetail {
-    fn default() -> Self {
-        ImageDetail::Auto
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ReasoningEffort {
@@ -58,76 +53,51 @@ pub enum ReasoningEffort {
     Xhigh,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum ReasoningSummary {
     Concise,
     Detailed,
+    #[default]
     Auto,
 }
 
-impl Default for ReasoningSummary {
-    fn default() -> Self {
-        ReasoningSummary::Auto
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum ServiceTier {
+    #[default]
     Auto,
     Default,
     Flex,
     Priority,
 }
 
-impl Default for ServiceTier {
-    fn default() -> Self {
-        ServiceTier::Auto
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum ToolChoice {
     None,
+    #[default]
     Auto,
     Required,
 }
 
-impl Default for ToolChoice {
-    fn default() -> Self {
-        ToolChoice::Auto
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum Truncation {
+    #[default]
     Auto,
     Disabled,
 }
 
-impl Default for Truncation {
-    fn default() -> Self {
-        Truncation::Auto
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum Verbosity {
     Low,
+    #[default]
     Medium,
     High,
 }
 
-impl Default for Verbosity {
-    fn default() -> Self {
-        Verbosity::Medium
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum IncludeOption {
@@ -137,6 +107,31 @@ pub enum IncludeOption {
     MessageOutputTextLogprobs,
 }
 
+impl IncludeOption {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IncludeOption::ReasoningEncryptedContent => "reasoning.encrypted_content",
+            IncludeOption::MessageOutputTextLogprobs => "message.output_text.logprobs",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ListOrder {
+    Asc,
+    Desc,
+}
+
+impl ListOrder {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ListOrder::Asc => "asc",
+            ListOrder::Desc => "desc",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum TextFormatType {
@@ -159,6 +154,18 @@ pub enum ErrorType {
     TooManyRequests,
 }
 
+impl ErrorType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorType::ServerError => "server_error",
+            ErrorType::InvalidRequest => "invalid_request",
+            ErrorType::NotFound => "not_found",
+            ErrorType::ModelError => "model_error",
+            ErrorType::TooManyRequests => "too_many_requests",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ResponseStatus {
     Queued,
@@ -166,6 +173,7 @@ pub enum ResponseStatus {
     Completed,
     Failed,
     Incomplete,
+    Cancelled,
     Other(String),
 }
 
@@ -177,9 +185,20 @@ impl ResponseStatus {
             ResponseStatus::Completed => "completed",
             ResponseStatus::Failed => "failed",
             ResponseStatus::Incomplete => "incomplete",
+            ResponseStatus::Cancelled => "cancelled",
             ResponseStatus::Other(value) => value.as_str(),
         }
     }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            ResponseStatus::Completed
+                | ResponseStatus::Failed
+                | ResponseStatus::Incomplete
+                | ResponseStatus::Cancelled
+        )
+    }
 }
 
 impl fmt::Display for ResponseStatus {
@@ -209,6 +228,7 @@ impl<'de> Deserialize<'de> for ResponseStatus {
             "completed" => ResponseStatus::Completed,
             "failed" => ResponseStatus::Failed,
             "incomplete" => ResponseStatus::Incomplete,
+            "cancelled" => ResponseStatus::Cancelled,
             _ => ResponseStatus::Other(value),
         })
     }
@@ -344,6 +344,133 @@ enum KnownStreamingEvent {
     },
 }
 
+impl StreamingEvent {
+    pub fn sequence_number(&self) -> Option<i32> {
+        match self {
+            StreamingEvent::ResponseCreated { sequence_number, .. }
+            | StreamingEvent::ResponseQueued { sequence_number, .. }
+            | StreamingEvent::ResponseInProgress { sequence_number, .. }
+            | StreamingEvent::ResponseCompleted { sequence_number, .. }
+            | StreamingEvent::ResponseFailed { sequence_number, .. }
+            | StreamingEvent::ResponseIncomplete { sequence_number, .. }
+            | StreamingEvent::OutputItemAdded { sequence_number, .. }
+            | StreamingEvent::OutputItemDone { sequence_number, .. }
+            | StreamingEvent::ContentPartAdded { sequence_number, .. }
+            | StreamingEvent::ContentPartDone { sequence_number, .. }
+            | StreamingEvent::OutputTextDelta { sequence_number, .. }
+            | StreamingEvent::OutputTextDone { sequence_number, .. }
+            | StreamingEvent::RefusalDelta { sequence_number, .. }
+            | StreamingEvent::RefusalDone { sequence_number, .. }
+            | StreamingEvent::ReasoningDelta { sequence_number, .. }
+            | StreamingEvent::ReasoningDone { sequence_number, .. }
+            | StreamingEvent::ReasoningSummaryDelta { sequence_number, .. }
+            | StreamingEvent::ReasoningSummaryDone { sequence_number, .. }
+            | StreamingEvent::ReasoningSummaryPartAdded { sequence_number, .. }
+            | StreamingEvent::ReasoningSummaryPartDone { sequence_number, .. }
+            | StreamingEvent::OutputTextAnnotationAdded { sequence_number, .. }
+            | StreamingEvent::FunctionCallArgumentsDelta { sequence_number, .. }
+            | StreamingEvent::FunctionCallArgumentsDone { sequence_number, .. }
+            | StreamingEvent::Error { sequence_number, .. } => Some(*sequence_number),
+            StreamingEvent::Unknown { sequence_number, .. } => *sequence_number,
+            StreamingEvent::Done => None,
+        }
+    }
+
+    pub(crate) fn set_sequence_number(&mut self, value: i32) {
+        match self {
+            StreamingEvent::ResponseCreated { sequence_number, .. }
+            | StreamingEvent::ResponseQueued { sequence_number, .. }
+            | StreamingEvent::ResponseInProgress { sequence_number, .. }
+            | StreamingEvent::ResponseCompleted { sequence_number, .. }
+            | StreamingEvent::ResponseFailed { sequence_number, .. }
+            | StreamingEvent::ResponseIncomplete { sequence_number, .. }
+            | StreamingEvent::OutputItemAdded { sequence_number, .. }
+            | StreamingEvent::OutputItemDone { sequence_number, .. }
+            | StreamingEvent::ContentPartAdded { sequence_number, .. }
+            | StreamingEvent::ContentPartDone { sequence_number, .. }
+            | StreamingEvent::OutputTextDelta { sequence_number, .. }
+            | StreamingEvent::OutputTextDone { sequence_number, .. }
+            | StreamingEvent::RefusalDelta { sequence_number, .. }
+            | StreamingEvent::RefusalDone { sequence_number, .. }
+            | StreamingEvent::ReasoningDelta { sequence_number, .. }
+            | StreamingEvent::ReasoningDone { sequence_number, .. }
+            | StreamingEvent::ReasoningSummaryDelta { sequence_number, .. }
+            | StreamingEvent::ReasoningSummaryDone { sequence_number, .. }
+            | StreamingEvent::ReasoningSummaryPartAdded { sequence_number, .. }
+            | StreamingEvent::ReasoningSummaryPartDone { sequence_number, .. }
+            | StreamingEvent::OutputTextAnnotationAdded { sequence_number, .. }
+            | StreamingEvent::FunctionCallArgumentsDelta { sequence_number, .. }
+            | StreamingEvent::FunctionCallArgumentsDone { sequence_number, .. }
+            | StreamingEvent::Error { sequence_number, .. } => *sequence_number = value,
+            StreamingEvent::Unknown {
+                sequence_number,
+                raw,
+                ..
+            } => {
+                *sequence_number = Some(value);
+                if let Value::Object(fields) = raw {
+                    fields.insert("sequence_number".to_string(), Value::from(value));
+                }
+            }
+            StreamingEvent::Done => {}
+        }
+    }
+
+    pub fn response(&self) -> Option<&ResponseResource> {
+        match self {
+            StreamingEvent::ResponseCreated { response, .. }
+            | StreamingEvent::ResponseQueued { response, .. }
+            | StreamingEvent::ResponseInProgress { response, .. }
+            | StreamingEvent::ResponseCompleted { response, .. }
+            | StreamingEvent::ResponseFailed { response, .. }
+            | StreamingEvent::ResponseIncomplete { response, .. } => Some(response),
+            _ => None,
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            StreamingEvent::ResponseCompleted { .. }
+                | StreamingEvent::ResponseFailed { .. }
+                | StreamingEvent::ResponseIncomplete { .. }
+                | StreamingEvent::Done
+        )
+    }
+
+    /// The SSE event name, e.g. `response.output_text.delta`. `Done` maps to `[DONE]`.
+    pub fn event_type(&self) -> &str {
+        match self {
+            StreamingEvent::ResponseCreated { .. } => "response.created",
+            StreamingEvent::ResponseQueued { .. } => "response.queued",
+            StreamingEvent::ResponseInProgress { .. } => "response.in_progress",
+            StreamingEvent::ResponseCompleted { .. } => "response.completed",
+            StreamingEvent::ResponseFailed { .. } => "response.failed",
+            StreamingEvent::ResponseIncomplete { .. } => "response.incomplete",
+            StreamingEvent::OutputItemAdded { .. } => "response.output_item.added",
+            StreamingEvent::OutputItemDone { .. } => "response.output_item.done",
+            StreamingEvent::ContentPartAdded { .. } => "response.content_part.added",
+            StreamingEvent::ContentPartDone { .. } => "response.content_part.done",
+            StreamingEvent::OutputTextDelta { .. } => "response.output_text.delta",
+            StreamingEvent::OutputTextDone { .. } => "response.output_text.done",
+            StreamingEvent::RefusalDelta { .. } => "response.refusal.delta",
+            StreamingEvent::RefusalDone { .. } => "response.refusal.done",
+            StreamingEvent::ReasoningDelta { .. } => "response.reasoning.delta",
+            StreamingEvent::ReasoningDone { .. } => "response.reasoning.done",
+            StreamingEvent::ReasoningSummaryDelta { .. } => "response.reasoning_summary_text.delta",
+            StreamingEvent::ReasoningSummaryDone { .. } => "response.reasoning_summary_text.done",
+            StreamingEvent::ReasoningSummaryPartAdded { .. } => "response.reasoning_summary_part.added",
+            StreamingEvent::ReasoningSummaryPartDone { .. } => "response.reasoning_summary_part.done",
+            StreamingEvent::OutputTextAnnotationAdded { .. } => "response.output_text.annotation.added",
+            StreamingEvent::FunctionCallArgumentsDelta { .. } => "response.function_call_arguments.delta",
+            StreamingEvent::FunctionCallArgumentsDone { .. } => "response.function_call_arguments.done",
+            StreamingEvent::Error { .. } => "error",
+            StreamingEvent::Unknown { event_type, .. } => event_type,
+            StreamingEvent::Done => "[DONE]",
+        }
+    }
+}
+
 impl Serialize for StreamingEvent {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
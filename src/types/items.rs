@@ -1,6 +1,18 @@
 use super::{content::*, enums::*};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::{Map, Value};
+use thiserror::Error;
+
+/// Why [`Item::parse_arguments`] could not produce typed arguments.
+#[derive(Error, Debug)]
+pub enum ArgumentsError {
+    #[error("Item is not a function call")]
+    NotAFunctionCall,
+
+    #[error("Invalid function call arguments: {0}")]
+    Invalid(#[from] serde_json::Error),
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Item {
@@ -128,6 +140,21 @@ impl Item {
         }
     }
 
+    /// Deserializes the `arguments` of an [`Item::FunctionCall`] into `T`.
+    /// Any other item is [`ArgumentsError::NotAFunctionCall`]. Empty arguments
+    /// parse as `{}`.
+    pub fn parse_arguments<T: DeserializeOwned>(&self) -> Result<T, ArgumentsError> {
+        let Item::FunctionCall { arguments, .. } = self else {
+            return Err(ArgumentsError::NotAFunctionCall);
+        };
+        let arguments = if arguments.trim().is_empty() {
+            "{}"
+        } else {
+            arguments
+        };
+        Ok(serde_json::from_str(arguments)?)
+    }
+
     pub fn validate(&self) -> Result<(), String> {
         match self {
             Item::Message { role, content, .. } => validate_message_content(role, content),
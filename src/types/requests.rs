@@ -111,3 +111,56 @@ pub struct ReasoningConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<ReasoningSummary>,
 }
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ListInputItemsParams {
+    pub after: Option<String>,
+    pub limit: Option<u32>,
+    pub order: Option<ListOrder>,
+    pub include: Option<Vec<IncludeOption>>,
+}
+
+impl ListInputItemsParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn after<S: Into<String>>(mut self, after: S) -> Self {
+        self.after = Some(after.into());
+        self
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn order(mut self, order: ListOrder) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    pub fn include(mut self, include: Vec<IncludeOption>) -> Self {
+        self.include = Some(include);
+        self
+    }
+
+    pub fn to_query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(after) = &self.after {
+            pairs.push(("after", after.clone()));
+        }
+        if let Some(limit) = self.limit {
+            pairs.push(("limit", limit.to_string()));
+        }
+        if let Some(order) = &self.order {
+            pairs.push(("order", order.as_str().to_string()));
+        }
+        if let Some(include) = &self.include {
+            for option in include {
+                pairs.push(("include[]", option.as_str().to_string()));
+            }
+        }
+        pairs
+    }
+}
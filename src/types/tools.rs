@@ -75,6 +75,29 @@ impl Tool {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl Tool {
+    /// Function tool whose `parameters` are the JSON Schema of `T`, rewritten
+    /// with [`strict_schema`](crate::schema::strict_schema) and sent with
+    /// `strict: true`. The doc comment of `T` becomes the description.
+    pub fn function_for<T: schemars::JsonSchema>(name: impl Into<String>) -> Self {
+        let mut parameters = crate::schema::parameters_for::<T>();
+        let description = parameters
+            .as_object_mut()
+            .and_then(|schema| schema.remove("description"));
+        let description = match description {
+            Some(Value::String(description)) => Some(description),
+            _ => None,
+        };
+        Tool::Function {
+            name: name.into(),
+            description,
+            parameters: Some(parameters),
+            strict: Some(true),
+        }
+    }
+}
+
 impl Serialize for Tool {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
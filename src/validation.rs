@@ -0,0 +1,175 @@
+use eventsource_stream::Event;
+use serde_json::Value;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::streaming::{RawSseEvent, StreamValidator, StreamingError};
+use crate::types::StreamingEvent;
+
+/// How a stream reacts to events that break the Open Responses streaming rules.
+///
+/// Payloads that cannot be parsed at all are reported as errors in every mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// Fails the stream on the first violation.
+    #[default]
+    Strict,
+    /// Records violations in the stream's [`ViolationReport`] and keeps streaming.
+    Warn,
+    /// Skips validation entirely.
+    Off,
+}
+
+/// A streaming rule broken by the server, recorded in [`ValidationMode::Warn`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolViolation {
+    /// Event type from the payload, or `[DONE]`.
+    pub event_type: String,
+    pub sequence_number: Option<i32>,
+    pub message: String,
+}
+
+impl fmt::Display for ProtocolViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.sequence_number {
+            Some(sequence_number) => write!(
+                f,
+                "{} (#{sequence_number}): {}",
+                self.event_type, self.message
+            ),
+            None => write!(f, "{}: {}", self.event_type, self.message),
+        }
+    }
+}
+
+/// Violations observed on a stream so far. Clones share the same list, so a
+/// handle taken before the stream is consumed can be read after it ends.
+#[derive(Debug, Clone, Default)]
+pub struct ViolationReport {
+    violations: Arc<Mutex<Vec<ProtocolViolation>>>,
+}
+
+impl ViolationReport {
+    pub fn violations(&self) -> Vec<ProtocolViolation> {
+        self.violations.lock().unwrap().clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.violations.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn record(&self, violation: ProtocolViolation) {
+        self.violations.lock().unwrap().push(violation);
+    }
+}
+
+pub(crate) type ViolationCallback = Arc<dyn Fn(&ProtocolViolation) + Send + Sync>;
+
+#[derive(Clone, Default)]
+pub(crate) struct ValidationPolicy {
+    pub(crate) mode: ValidationMode,
+    pub(crate) on_violation: Option<ViolationCallback>,
+}
+
+/// Turns SSE frames into [`StreamingEvent`]s, applying a [`ValidationPolicy`].
+pub(crate) struct EventParser {
+    validator: StreamValidator,
+    policy: ValidationPolicy,
+    report: ViolationReport,
+}
+
+impl EventParser {
+    pub(crate) fn new(policy: ValidationPolicy) -> Self {
+        Self {
+            validator: StreamValidator::default(),
+            policy,
+            report: ViolationReport::default(),
+        }
+    }
+
+    pub(crate) fn report(&self) -> ViolationReport {
+        self.report.clone()
+    }
+
+    pub(crate) fn parse(&mut self, event: Event) -> Result<StreamingEvent, StreamingError> {
+        self.parse_frame(&event.event, &event.data)
+    }
+
+    pub(crate) fn parse_raw(&mut self, event: &RawSseEvent) -> Result<StreamingEvent, StreamingError> {
+        self.parse_frame(event.event.as_deref().unwrap_or_default(), &event.data)
+    }
+
+    fn parse_frame(&mut self, event_name: &str, data: &str) -> Result<StreamingEvent, StreamingError> {
+        if data == "[DONE]" {
+            let observed = self.observe(&StreamingEvent::Done);
+            self.check(observed, "[DONE]", None)?;
+            return Ok(StreamingEvent::Done);
+        }
+
+        let raw_value: Value = serde_json::from_str(data)?;
+        let body_type = raw_value
+            .get("type")
+            .and_then(Value::as_str)
+            .ok_or_else(|| StreamingError::StreamError("Streaming payload is missing `type`".to_string()))?
+            .to_string();
+        let sequence_number = raw_value
+            .get("sequence_number")
+            .and_then(Value::as_i64)
+            .and_then(|number| i32::try_from(number).ok());
+
+        // Frames without an `event:` line are dispatched with the SSE default name.
+        let named = if event_name.is_empty() || event_name == "message" {
+            Err(StreamingError::MissingEventType {
+                body_type: body_type.clone(),
+            })
+        } else if event_name != body_type {
+            Err(StreamingError::EventTypeMismatch {
+                sse_event: event_name.to_string(),
+                body_type: body_type.clone(),
+            })
+        } else {
+            Ok(())
+        };
+        self.check(named, &body_type, sequence_number)?;
+
+        let parsed = serde_json::from_value::<StreamingEvent>(raw_value)?;
+        let observed = self.observe(&parsed);
+        self.check(observed, &body_type, sequence_number)?;
+        Ok(parsed)
+    }
+
+    fn observe(&mut self, event: &StreamingEvent) -> Result<(), StreamingError> {
+        match self.policy.mode {
+            ValidationMode::Off => Ok(()),
+            ValidationMode::Strict | ValidationMode::Warn => self.validator.observe(event),
+        }
+    }
+
+    fn check(
+        &self,
+        result: Result<(), StreamingError>,
+        event_type: &str,
+        sequence_number: Option<i32>,
+    ) -> Result<(), StreamingError> {
+        let error = match (result, self.policy.mode) {
+            (Ok(()), _) | (Err(_), ValidationMode::Off) => return Ok(()),
+            (Err(error), ValidationMode::Strict) => return Err(error),
+            (Err(error), ValidationMode::Warn) => error,
+        };
+
+        let violation = ProtocolViolation {
+            event_type: event_type.to_string(),
+            sequence_number,
+            message: error.to_string(),
+        };
+        if let Some(callback) = &self.policy.on_violation {
+            callback(&violation);
+        }
+        self.report.record(violation);
+        Ok(())
+    }
+}
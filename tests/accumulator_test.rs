@@ -0,0 +1,192 @@
+mod common;
+
+use common::{response_json, sse_body, text_message_events};
+use futures::StreamExt;
+use mockito::Server;
+use openresponses_rust::{
+    AccumulatorError, CreateResponseBody, Item, MessageContent, ResponseStatus,
+    ResponseStreamExt, StreamAccumulator, StreamingClient, StreamingError, StreamingEvent,
+};
+use serde_json::{Value, json};
+
+fn events(values: &[Value]) -> Vec<StreamingEvent> {
+    values
+        .iter()
+        .map(|value| serde_json::from_value(value.clone()).unwrap())
+        .collect()
+}
+
+fn message_text(item: &Item) -> &str {
+    match item {
+        Item::Message { content, .. } => match content.as_slice() {
+            [MessageContent::OutputText { text, .. }] => text,
+            other => panic!("unexpected content {other:?}"),
+        },
+        other => panic!("expected message, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_accumulate_adapter_exposes_snapshots() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(&text_message_events("resp_123", &["Hel", "lo"]), true))
+        .create_async()
+        .await;
+
+    let client = StreamingClient::with_base_url("test-key", server.url());
+    let stream = client
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap();
+
+    let accumulated: Vec<_> = stream
+        .accumulate()
+        .map(|event| event.unwrap())
+        .collect()
+        .await;
+
+    let first_delta = &accumulated[3];
+    assert!(matches!(first_delta.event, StreamingEvent::OutputTextDelta { .. }));
+    assert_eq!(message_text(&first_delta.snapshot.as_ref().unwrap().output[0]), "Hel");
+
+    let completed = accumulated
+        .iter()
+        .find(|event| matches!(event.event, StreamingEvent::ResponseCompleted { .. }))
+        .unwrap();
+    let response = completed.snapshot.as_ref().unwrap();
+    assert_eq!(response.status, ResponseStatus::Completed);
+    assert_eq!(message_text(&response.output[0]), "Hello");
+}
+
+#[tokio::test]
+async fn test_accumulate_reuses_snapshots_that_were_dropped() {
+    let source = events(&text_message_events("resp_123", &["Hel", "lo"]));
+    let mut stream = futures::stream::iter(source.into_iter().map(Ok)).accumulate();
+
+    let mut previous = None;
+    let mut deltas = 0;
+    while let Some(event) = stream.next().await {
+        let event = event.unwrap();
+        let Some(snapshot) = event.snapshot else {
+            continue;
+        };
+        let address = std::sync::Arc::as_ptr(&snapshot);
+        drop(snapshot);
+        // Deltas edit the previous snapshot in place once it has been dropped.
+        if matches!(event.event, StreamingEvent::OutputTextDelta { .. }) {
+            assert_eq!(Some(address), previous);
+            deltas += 1;
+        }
+        previous = Some(address);
+    }
+    assert_eq!(deltas, 2);
+    assert_eq!(message_text(&stream.accumulator().snapshot().unwrap().output[0]), "Hello");
+}
+
+#[test]
+fn test_accumulator_assembles_function_calls_and_reasoning() {
+    let mut completed = response_json("resp_1", "completed");
+    completed["output"] = json!([
+        {
+            "type": "reasoning",
+            "id": "rs_1",
+            "summary": [{"type": "summary_text", "text": "Need weather."}]
+        },
+        {
+            "type": "function_call",
+            "id": "fc_1",
+            "call_id": "call_1",
+            "name": "get_weather",
+            "arguments": "{\"city\":\"Seoul\"}",
+            "status": "completed"
+        }
+    ]);
+
+    let events = events(&[
+        json!({"type": "response.created", "sequence_number": 1, "response": response_json("resp_1", "in_progress")}),
+        json!({"type": "response.output_item.added", "sequence_number": 2, "output_index": 0,
+            "item": {"type": "reasoning", "id": "rs_1", "summary": []}}),
+        json!({"type": "response.reasoning_summary_part.added", "sequence_number": 3, "item_id": "rs_1",
+            "output_index": 0, "summary_index": 0, "part": {"type": "summary_text", "text": ""}}),
+        json!({"type": "response.reasoning_summary_text.delta", "sequence_number": 4, "item_id": "rs_1",
+            "output_index": 0, "summary_index": 0, "delta": "Need "}),
+        json!({"type": "response.reasoning_summary_text.delta", "sequence_number": 5, "item_id": "rs_1",
+            "output_index": 0, "summary_index": 0, "delta": "weather."}),
+        json!({"type": "response.output_item.added", "sequence_number": 6, "output_index": 1,
+            "item": {"type": "function_call", "id": "fc_1", "call_id": "call_1", "name": "get_weather",
+                "arguments": "", "status": "in_progress"}}),
+        json!({"type": "response.function_call_arguments.delta", "sequence_number": 7, "item_id": "fc_1",
+            "output_index": 1, "delta": "{\"city\":"}),
+        json!({"type": "response.function_call_arguments.delta", "sequence_number": 8, "item_id": "fc_1",
+            "output_index": 1, "delta": "\"Seoul\"}"}),
+        json!({"type": "response.completed", "sequence_number": 9, "response": completed}),
+    ]);
+
+    let mut accumulator = StreamAccumulator::new();
+    for event in &events[..events.len() - 1] {
+        assert!(accumulator.apply(event).unwrap().is_none());
+    }
+
+    let snapshot = accumulator.snapshot().unwrap();
+    assert!(matches!(
+        &snapshot.output[0],
+        Item::Reasoning { summary, .. }
+            if matches!(summary.as_slice(), [MessageContent::SummaryText { text }] if text == "Need weather.")
+    ));
+    assert!(matches!(
+        &snapshot.output[1],
+        Item::FunctionCall { arguments, status: Some(_), .. } if arguments == "{\"city\":\"Seoul\"}"
+    ));
+
+    let response = accumulator.apply(events.last().unwrap()).unwrap().unwrap();
+    assert_eq!(response.status, ResponseStatus::Completed);
+    assert!(accumulator.is_finished());
+}
+
+#[test]
+fn test_accumulator_reports_mismatch_with_final_payload() {
+    let mut values = text_message_events("resp_1", &["Hel", "lo"]);
+    let completed = values.last_mut().unwrap();
+    completed["response"]["output"][0]["content"][0]["text"] = json!("Goodbye");
+
+    let mut accumulator = StreamAccumulator::new();
+    let mut result = Ok(None);
+    for event in &events(&values) {
+        result = accumulator.apply(event).map(|response| response.cloned());
+        if result.is_err() {
+            break;
+        }
+    }
+
+    assert!(matches!(result, Err(AccumulatorError::Mismatch { output_index: 0, .. })));
+    // What was streamed survives the mismatch.
+    assert!(!accumulator.is_finished());
+    assert_eq!(message_text(&accumulator.snapshot().unwrap().output[0]), "Hello");
+    assert_eq!(accumulator.into_response().unwrap().id, "resp_1");
+}
+
+#[tokio::test]
+async fn test_accumulate_adapter_stops_after_error() {
+    let values = vec![json!({
+        "type": "response.output_text.delta",
+        "sequence_number": 1,
+        "item_id": "msg_1",
+        "output_index": 0,
+        "content_index": 0,
+        "delta": "orphan"
+    })];
+    let items: Vec<Result<StreamingEvent, StreamingError>> =
+        events(&values).into_iter().map(Ok).collect();
+
+    let results: Vec<_> = futures::stream::iter(items).accumulate().collect().await;
+
+    assert_eq!(results.len(), 1);
+    assert!(matches!(
+        results[0],
+        Err(StreamingError::Accumulator(AccumulatorError::MissingResponse { .. }))
+    ));
+}
@@ -0,0 +1,231 @@
+mod common;
+
+use common::{response_json, sse_body, text_message_events};
+use futures::StreamExt;
+use mockito::{Matcher, Server};
+use openresponses_rust::{
+    ApiKeyAuth, AuthError, AuthProvider, Client, ClientError, CommandTokenSource,
+    CreateResponseBody, NoAuth, RefreshingTokenAuth, StreamingClient,
+};
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+fn token_file(name: &str, token: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("openresponses-{}-{name}", std::process::id()));
+    std::fs::write(&path, token).unwrap();
+    path
+}
+
+async fn completed_mock(server: &mut mockito::ServerGuard, header: &str, value: Matcher) -> mockito::Mock {
+    server
+        .mock("POST", "/v1/responses")
+        .match_header(header, value)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_123", "completed").to_string())
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn test_api_key_header_auth_replaces_bearer() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/responses")
+        .match_header("authorization", Matcher::Missing)
+        .match_header("api-key", "azure-key")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_123", "completed").to_string())
+        .create_async()
+        .await;
+
+    let client = Client::builder("unused")
+        .base_url(server.url())
+        .auth(ApiKeyAuth::new("azure-key"))
+        .build();
+
+    client.create_response(CreateResponseBody::default()).await.unwrap();
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_no_auth_sends_no_credentials() {
+    let mut server = Server::new_async().await;
+    let mock = completed_mock(&mut server, "authorization", Matcher::Missing).await;
+
+    let client = Client::builder("").base_url(server.url()).auth(NoAuth).build();
+
+    client.create_response(CreateResponseBody::default()).await.unwrap();
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_refreshing_token_picks_up_rotated_file() {
+    let mut server = Server::new_async().await;
+    let first = completed_mock(&mut server, "authorization", "Bearer token-a".into()).await;
+    let second = completed_mock(&mut server, "authorization", "Bearer token-b".into()).await;
+
+    let path = token_file("rotate", "token-a\n");
+    let client = Client::builder("unused")
+        .base_url(server.url())
+        .auth(RefreshingTokenAuth::from_file(&path).default_ttl(Duration::ZERO))
+        .build();
+
+    client.create_response(CreateResponseBody::default()).await.unwrap();
+    std::fs::write(&path, r#"{"access_token": "token-b", "expires_in": 3600}"#).unwrap();
+    client.create_response(CreateResponseBody::default()).await.unwrap();
+
+    first.assert_async().await;
+    second.assert_async().await;
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn test_unauthorized_response_invalidates_cached_token() {
+    let mut server = Server::new_async().await;
+    // The rejected call is retried once, and the file still holds the stale token.
+    let rejected = server
+        .mock("POST", "/v1/responses")
+        .match_header("authorization", "Bearer stale")
+        .with_status(401)
+        .with_body(r#"{"error":{"message":"expired","type":"invalid_request_error"}}"#)
+        .expect(2)
+        .create_async()
+        .await;
+    let accepted = completed_mock(&mut server, "authorization", "Bearer fresh".into()).await;
+
+    let path = token_file("invalidate", "stale");
+    let client = Client::builder("unused")
+        .base_url(server.url())
+        .auth(RefreshingTokenAuth::from_file(&path))
+        .build();
+
+    let error = client.create_response(CreateResponseBody::default()).await.unwrap_err();
+    assert!(matches!(error, ClientError::ApiError { status_code: 401, .. }));
+
+    std::fs::write(&path, "fresh").unwrap();
+    client.create_response(CreateResponseBody::default()).await.unwrap();
+
+    rejected.assert_async().await;
+    accepted.assert_async().await;
+    std::fs::remove_file(path).unwrap();
+}
+
+/// Hands out `token-N`, moving to the next token whenever it is invalidated.
+#[derive(Default)]
+struct RotatingAuth {
+    invalidations: AtomicUsize,
+}
+
+#[async_trait::async_trait]
+impl AuthProvider for RotatingAuth {
+    async fn headers(&self) -> Result<HeaderMap, AuthError> {
+        let token = format!("Bearer token-{}", self.invalidations.load(Ordering::SeqCst));
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(&token).unwrap());
+        Ok(headers)
+    }
+
+    async fn invalidate(&self) {
+        self.invalidations.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn test_unauthorized_call_is_retried_once_with_fresh_credentials() {
+    let mut server = Server::new_async().await;
+    let rejected = server
+        .mock("POST", "/v1/responses")
+        .match_header("authorization", "Bearer token-0")
+        .with_status(401)
+        .create_async()
+        .await;
+    let accepted = completed_mock(&mut server, "authorization", "Bearer token-1".into()).await;
+
+    let auth = Arc::new(RotatingAuth::default());
+    let client = Client::builder("unused")
+        .base_url(server.url())
+        .auth(auth.clone())
+        .build();
+    client.create_response(CreateResponseBody::default()).await.unwrap();
+    assert_eq!(auth.invalidations.load(Ordering::SeqCst), 1);
+    rejected.assert_async().await;
+    accepted.assert_async().await;
+
+    let mut server = Server::new_async().await;
+    let rejected = server
+        .mock("POST", "/v1/responses")
+        .match_header("authorization", "Bearer token-1")
+        .with_status(401)
+        .create_async()
+        .await;
+    let accepted = server
+        .mock("POST", "/v1/responses")
+        .match_header("authorization", "Bearer token-2")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(&text_message_events("resp_123", &["Hi"]), true))
+        .create_async()
+        .await;
+
+    let streaming = StreamingClient::builder("unused")
+        .base_url(server.url())
+        .auth(auth.clone())
+        .build();
+    let events: Vec<_> = streaming
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert!(events.iter().all(Result::is_ok));
+    assert_eq!(auth.invalidations.load(Ordering::SeqCst), 2);
+    rejected.assert_async().await;
+    accepted.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_streaming_client_uses_auth_provider() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/responses")
+        .match_header("api-key", "azure-key")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(&text_message_events("resp_123", &["Hi"]), true))
+        .create_async()
+        .await;
+
+    let client = StreamingClient::builder("unused")
+        .base_url(server.url())
+        .auth(ApiKeyAuth::new("azure-key"))
+        .build();
+
+    let events: Vec<_> = client
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert!(events.iter().all(Result::is_ok));
+    mock.assert_async().await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_command_token_source_errors_are_reported() {
+    let client = Client::builder("unused")
+        .base_url("http://127.0.0.1:9")
+        .auth(RefreshingTokenAuth::new(
+            CommandTokenSource::new("sh").arg("-c").arg("echo boom >&2; exit 3"),
+        ))
+        .build();
+
+    let error = client.create_response(CreateResponseBody::default()).await.unwrap_err();
+    assert!(matches!(error, ClientError::Auth(ref error) if error.to_string().contains("boom")));
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use common::{sse_body, text_message_events};
+use futures::StreamExt;
+use mockito::Server;
+use openresponses_rust::{
+    Cassette, CassetteError, CreateResponseBody, RawSseEvent, ReplayOptions, StreamingClient,
+    StreamingError, StreamingEvent, ValidationMode,
+};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("openresponses-{}-{name}", std::process::id()))
+}
+
+#[tokio::test]
+async fn test_recorded_cassette_replays_like_the_live_stream() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(&text_message_events("resp_123", &["Hel", "lo"]), true))
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = StreamingClient::with_base_url("test-key", server.url());
+    let mut recorder = Cassette::record(
+        client
+            .stream_response_lines(CreateResponseBody::default())
+            .await
+            .unwrap(),
+    );
+    while let Some(event) = recorder.next().await {
+        event.unwrap();
+    }
+    let cassette = recorder.into_cassette();
+
+    let live: Vec<StreamingEvent> = client
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    for name in ["cassette.jsonl", "cassette.sse"] {
+        let path = temp_path(name);
+        cassette.save(&path).unwrap();
+        let loaded = Cassette::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, cassette, "{name}");
+        let replayed: Vec<StreamingEvent> = loaded.replay().map(Result::unwrap).collect().await;
+        assert_eq!(replayed, live, "{name}");
+    }
+}
+
+#[tokio::test]
+async fn test_sse_transcript_replay_is_validated() {
+    let transcript = concat!(
+        ": captured from a local server\n",
+        ": elapsed_ms=5\n",
+        "event: acme:trace_event\n",
+        "data: {\"type\":\"acme:trace_event\",\n",
+        "data: \"sequence_number\":1}\n",
+        "\n",
+        ": elapsed_ms=9\n",
+        "event: acme:trace_event\n",
+        "data: {\"type\":\"acme:trace_event\",\"sequence_number\":3}\n",
+        "\n",
+        "data: [DONE]\n",
+    );
+    let cassette = Cassette::from_sse(transcript);
+
+    assert_eq!(cassette.len(), 3);
+    assert_eq!(
+        cassette.events()[0].event,
+        RawSseEvent {
+            event: Some("acme:trace_event".to_string()),
+            data: "{\"type\":\"acme:trace_event\",\n\"sequence_number\":1}".to_string(),
+        }
+    );
+    assert_eq!(cassette.events()[2].elapsed, Duration::from_millis(9));
+
+    let strict: Vec<_> = cassette.replay().collect().await;
+    assert!(matches!(
+        strict[1],
+        Err(StreamingError::SequenceError { previous: 1, received: 3 })
+    ));
+
+    let mut warn = cassette.replay_with(ReplayOptions::new().validation_mode(ValidationMode::Warn));
+    while let Some(event) = warn.next().await {
+        event.unwrap();
+    }
+    assert_eq!(warn.violations().len(), 1);
+    assert_eq!(warn.last_sequence_number(), Some(3));
+}
+
+#[tokio::test]
+async fn test_realtime_replay_reproduces_delays() {
+    let cassette = Cassette::from_jsonl(concat!(
+        "{\"elapsed_ms\":0,\"event\":\"acme:trace_event\",\"data\":\"{\\\"type\\\":\\\"acme:trace_event\\\",\\\"sequence_number\\\":1}\"}\n",
+        "{\"elapsed_ms\":60,\"data\":\"[DONE]\"}\n",
+    ))
+    .unwrap();
+
+    let started = Instant::now();
+    let events: Vec<_> = cassette
+        .replay_with(ReplayOptions::new().realtime(true))
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    assert!(started.elapsed() >= Duration::from_millis(60));
+    assert!(matches!(events.as_slice(), [StreamingEvent::Unknown { .. }, StreamingEvent::Done]));
+}
+
+#[test]
+fn test_cassette_format_errors() {
+    assert!(matches!(
+        Cassette::new().save(temp_path("cassette.txt")),
+        Err(CassetteError::UnsupportedFormat { .. })
+    ));
+    assert!(matches!(
+        Cassette::from_jsonl("{\"elapsed_ms\":0,\"data\":\"[DONE]\"}\nnot json\n"),
+        Err(CassetteError::Json { line: 2, .. })
+    ));
+}
@@ -0,0 +1,135 @@
+#![allow(dead_code)]
+
+use serde_json::{Value, json};
+
+pub fn response_json(id: &str, status: &str) -> Value {
+    json!({
+        "id": id,
+        "object": "response",
+        "created_at": 1_700_000_000,
+        "status": status,
+        "model": "gpt-4o",
+        "output": [],
+        "tools": [],
+        "tool_choice": "auto",
+        "truncation": "disabled",
+        "parallel_tool_calls": true,
+        "text": {"format": {"type": "text"}},
+        "top_p": 1.0,
+        "presence_penalty": 0.0,
+        "frequency_penalty": 0.0,
+        "top_logprobs": 0,
+        "temperature": 1.0,
+        "store": true,
+        "background": false,
+        "service_tier": "default",
+        "metadata": {}
+    })
+}
+
+pub fn not_found_body() -> &'static str {
+    r#"{
+        "error": {
+            "message": "Response not found.",
+            "type": "invalid_request_error",
+            "param": null,
+            "code": "not_found"
+        }
+    }"#
+}
+
+pub fn sse_frame(payload: &Value) -> String {
+    let event_type = payload["type"].as_str().expect("payload must have a type");
+    format!("event: {event_type}\ndata: {payload}\n\n")
+}
+
+pub fn sse_body(payloads: &[Value], done: bool) -> String {
+    let mut body: String = payloads.iter().map(sse_frame).collect();
+    if done {
+        body.push_str("data: [DONE]\n\n");
+    }
+    body
+}
+
+pub fn text_message_events(response_id: &str, text_chunks: &[&str]) -> Vec<Value> {
+    let mut sequence_number = 0;
+    let mut next = || {
+        sequence_number += 1;
+        sequence_number
+    };
+    let full_text: String = text_chunks.concat();
+
+    let mut events = vec![
+        json!({
+            "type": "response.created",
+            "sequence_number": next(),
+            "response": response_json(response_id, "in_progress")
+        }),
+        json!({
+            "type": "response.output_item.added",
+            "sequence_number": next(),
+            "output_index": 0,
+            "item": {"type": "message", "id": "msg_1", "status": "in_progress", "role": "assistant", "content": []}
+        }),
+        json!({
+            "type": "response.content_part.added",
+            "sequence_number": next(),
+            "item_id": "msg_1",
+            "output_index": 0,
+            "content_index": 0,
+            "part": {"type": "output_text", "text": "", "annotations": []}
+        }),
+    ];
+
+    for chunk in text_chunks {
+        events.push(json!({
+            "type": "response.output_text.delta",
+            "sequence_number": next(),
+            "item_id": "msg_1",
+            "output_index": 0,
+            "content_index": 0,
+            "delta": chunk
+        }));
+    }
+
+    let mut completed = response_json(response_id, "completed");
+    completed["output"] = json!([{
+        "type": "message",
+        "id": "msg_1",
+        "status": "completed",
+        "role": "assistant",
+        "content": [{"type": "output_text", "text": full_text, "annotations": []}]
+    }]);
+
+    events.extend([
+        json!({
+            "type": "response.output_text.done",
+            "sequence_number": next(),
+            "item_id": "msg_1",
+            "output_index": 0,
+            "content_index": 0,
+            "text": full_text
+        }),
+        json!({
+            "type": "response.content_part.done",
+            "sequence_number": next(),
+            "item_id": "msg_1",
+            "output_index": 0,
+            "content_index": 0,
+            "part": {"type": "output_text", "text": full_text, "annotations": []}
+        }),
+        json!({
+            "type": "response.output_item.done",
+            "sequence_number": next(),
+            "output_index": 0,
+            "item": completed["output"][0].clone()
+        }),
+        json!({
+            "type": "response.completed",
+            "sequence_number": next(),
+            "response": completed
+        }),
+    ]);
+
+    events
+}
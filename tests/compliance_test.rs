@@ -0,0 +1,90 @@
+#![cfg(all(feature = "compliance", feature = "testing"))]
+
+use openresponses_rust::compliance::{ComplianceSuite, Outcome, Scenario};
+use openresponses_rust::server::ProviderError;
+use openresponses_rust::testing::{self, MockReply, MockServer};
+use openresponses_rust::{Input, Item, ResponsesClient, RetryPolicy};
+
+fn client(server: &MockServer) -> ResponsesClient {
+    ResponsesClient::builder("test-key")
+        .base_url(server.url())
+        .retry_policy(RetryPolicy::none())
+        .build()
+}
+
+fn text_reply(id: &str, text: &str) -> MockReply {
+    MockReply::response(testing::response(id, vec![Item::assistant_message(text)]))
+}
+
+#[tokio::test]
+async fn test_conforming_server_passes_every_scenario() {
+    let server = MockServer::start().await;
+    let mut follow_up = testing::response("resp_5", vec![Item::assistant_message("42")]);
+    follow_up.previous_response_id = Some("resp_4".to_string());
+    server
+        .enqueue(text_reply("resp_1", "Hello"))
+        .enqueue(text_reply("resp_2", "One, two, three, four, five."))
+        .enqueue(MockReply::response(testing::response(
+            "resp_3",
+            vec![testing::function_call("call_1", "get_weather", r#"{"city":"Seoul"}"#)],
+        )))
+        .enqueue(text_reply("resp_3b", "It is 21 degrees in Seoul."))
+        .enqueue(text_reply("resp_4", "Noted."))
+        .enqueue(MockReply::response(follow_up))
+        .enqueue(text_reply("resp_6", r#"{"name":"Seoul","country":"South Korea"}"#))
+        .enqueue(MockReply::error(
+            ProviderError::invalid_request("temperature must be at most 2").param("temperature"),
+        ));
+
+    let report = ComplianceSuite::new(client(&server), "mock-model").run().await;
+
+    assert!(report.is_compliant(), "{report}");
+    assert_eq!(report.passed().count(), Scenario::ALL.len());
+    assert!(report.to_string().ends_with("6 of 6 scenarios passed"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 8);
+    assert_eq!(requests[1].stream, Some(true));
+    assert!(matches!(
+        &requests[3].input,
+        Some(Input::Items(items)) if matches!(items.last(), Some(Item::FunctionCallOutput { call_id, .. }) if call_id == "call_1")
+    ));
+    assert_eq!(requests[5].previous_response_id.as_deref(), Some("resp_4"));
+}
+
+#[tokio::test]
+async fn test_report_explains_failed_scenarios() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(text_reply("resp_1", "Noted."))
+        .enqueue(text_reply("resp_2", "42"))
+        .enqueue(text_reply("resp_3", "Sure!"))
+        .enqueue(MockReply::raw_sse(
+            "data: {\"type\":\"response.created\",\"sequence_number\":0}\n\n",
+        ));
+
+    let report = ComplianceSuite::new(client(&server), "mock-model")
+        .scenarios(vec![
+            Scenario::PreviousResponseId,
+            Scenario::ErrorShape,
+            Scenario::Streaming,
+        ])
+        .run()
+        .await;
+
+    assert!(!report.is_compliant());
+    assert_eq!(report.failed().count(), 3);
+    assert!(matches!(
+        &report.result(Scenario::PreviousResponseId).unwrap().outcome,
+        Outcome::Failed(reason) if reason.contains("`previous_response_id` is None")
+    ));
+    assert!(matches!(
+        &report.result(Scenario::ErrorShape).unwrap().outcome,
+        Outcome::Failed(reason) if reason.contains("was accepted")
+    ));
+    assert!(matches!(
+        &report.result(Scenario::Streaming).unwrap().outcome,
+        Outcome::Failed(reason) if reason.starts_with("invalid stream")
+    ));
+    assert_eq!("error_shape".parse::<Scenario>(), Ok(Scenario::ErrorShape));
+}
@@ -174,6 +174,7 @@ fn assert_client_api_error(error: ClientError) {
             status_code,
             error,
             raw_body,
+            ..
         } => {
             assert_eq!(status_code, 400);
             let error = error.expect("expected parsed error body");
@@ -193,6 +194,7 @@ fn assert_streaming_api_error(error: StreamingError) {
             status_code,
             error,
             raw_body,
+            ..
         } => {
             assert_eq!(status_code, 400);
             let error = error.expect("expected parsed error body");
@@ -205,3 +207,37 @@ fn assert_streaming_api_error(error: StreamingError) {
         other => panic!("expected API error, got {other:?}"),
     }
 }
+
+#[tokio::test]
+async fn test_api_errors_carry_request_id() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/v1/responses")
+        .with_status(500)
+        .with_header("content-type", "application/json")
+        .with_header("x-request-id", "req_abc123")
+        .with_body(r#"{"error":{"message":"boom","type":"server_error"}}"#)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let error = client
+        .create_response(CreateResponseBody::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ClientError::ApiError { request_id: Some(ref id), .. } if id == "req_abc123"
+    ));
+
+    let client = StreamingClient::with_base_url("test-key", server.url());
+    let error = match client.stream_response(CreateResponseBody::default()).await {
+        Ok(_) => panic!("expected API error"),
+        Err(error) => error,
+    };
+    assert!(matches!(
+        error,
+        StreamingError::ApiError { request_id: Some(ref id), .. } if id == "req_abc123"
+    ));
+}
@@ -0,0 +1,253 @@
+mod common;
+
+use common::{response_json, sse_body, text_message_events};
+use futures::StreamExt;
+use mockito::{Matcher, Server};
+use openresponses_rust::{
+    BuildError, Client, ClientError, CreateResponseBody, RequestOptions, ResumeOptions, RetryPolicy,
+    StreamingClient, StreamingError,
+};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_default_organization_and_project_headers_are_sent() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/responses")
+        .match_header("http-referer", "https://example.com")
+        .match_header("x-title", "Example App")
+        .match_header("openai-organization", "org_123")
+        .match_header("openai-project", "proj_456")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_123", "completed").to_string())
+        .create_async()
+        .await;
+
+    let client = Client::builder("test-key")
+        .base_url(server.url())
+        .default_header("HTTP-Referer", "https://example.com")
+        .default_header("X-Title", "Example App")
+        .organization("org_123")
+        .project("proj_456")
+        .build();
+
+    client.create_response(CreateResponseBody::default()).await.unwrap();
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_request_options_add_headers_query_and_idempotency_key() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/responses")
+        .match_query(Matcher::UrlEncoded("tenant".into(), "acme corp".into()))
+        .match_header("x-tenant", "override")
+        .match_header("idempotency-key", "req-1")
+        .with_status(500)
+        .with_body("{}")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = Client::builder("test-key")
+        .base_url(server.url())
+        .default_header("X-Tenant", "default")
+        .retry_policy(RetryPolicy::new().max_attempts(2).base_delay(Duration::from_millis(1)))
+        .build();
+
+    let error = client
+        .create_response_with_options(
+            CreateResponseBody::default(),
+            RequestOptions::new()
+                .header("X-Tenant", "override")
+                .query("tenant", "acme corp")
+                .idempotency_key("req-1"),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ClientError::ApiError { status_code: 500, .. }));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_stream_request_options_add_headers() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/responses")
+        .match_query(Matcher::UrlEncoded("tenant".into(), "acme".into()))
+        .match_header("x-tenant", "acme")
+        .match_header("openai-project", "proj_456")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(&text_message_events("resp_123", &["Hi"]), true))
+        .create_async()
+        .await;
+
+    let client = StreamingClient::builder("test-key")
+        .base_url(server.url())
+        .project("proj_456")
+        .build();
+
+    let events: Vec<_> = client
+        .stream_response_with_options(
+            CreateResponseBody::default(),
+            RequestOptions::new().header("X-Tenant", "acme").query("tenant", "acme"),
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert!(events.iter().all(Result::is_ok));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_lifecycle_request_options_add_headers_and_query() {
+    let mut server = Server::new_async().await;
+    let get = server
+        .mock("GET", "/v1/responses/resp_123")
+        .match_query(Matcher::UrlEncoded("tenant".into(), "acme".into()))
+        .match_header("x-tenant", "acme")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_123", "in_progress").to_string())
+        .create_async()
+        .await;
+    let cancel = server
+        .mock("POST", "/v1/responses/resp_123/cancel")
+        .match_query(Matcher::UrlEncoded("tenant".into(), "acme".into()))
+        .match_header("x-tenant", "acme")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_123", "cancelled").to_string())
+        .create_async()
+        .await;
+    let delete = server
+        .mock("DELETE", "/v1/responses/resp_123")
+        .match_query(Matcher::UrlEncoded("tenant".into(), "acme".into()))
+        .match_header("x-tenant", "acme")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": "resp_123", "object": "response.deleted", "deleted": true}"#)
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let options = RequestOptions::new().header("X-Tenant", "acme").query("tenant", "acme");
+    client.get_response_with_options("resp_123", options.clone()).await.unwrap();
+    client.cancel_response_with_options("resp_123", options.clone()).await.unwrap();
+    client.delete_response_with_options("resp_123", options).await.unwrap();
+
+    get.assert_async().await;
+    cancel.assert_async().await;
+    delete.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_resumable_request_options_apply_to_reconnects() {
+    let mut server = Server::new_async().await;
+    let events = text_message_events("resp_123", &["Hel", "lo"]);
+    let (first, rest) = events.split_at(4);
+
+    let create = server
+        .mock("POST", "/v1/responses")
+        .match_query(Matcher::UrlEncoded("tenant".into(), "acme".into()))
+        .match_header("x-tenant", "acme")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(first, false))
+        .create_async()
+        .await;
+    let resume = server
+        .mock("GET", "/v1/responses/resp_123")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("tenant".into(), "acme".into()),
+            Matcher::UrlEncoded("stream".into(), "true".into()),
+            Matcher::UrlEncoded("starting_after".into(), "4".into()),
+        ]))
+        .match_header("x-tenant", "acme")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(rest, true))
+        .create_async()
+        .await;
+
+    let client = StreamingClient::with_base_url("test-key", server.url());
+    let events: Vec<_> = client
+        .stream_response_resumable_with_options(
+            CreateResponseBody::default(),
+            ResumeOptions::new().reconnect_delay(Duration::from_millis(1)),
+            RequestOptions::new().header("X-Tenant", "acme").query("tenant", "acme"),
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert!(events.iter().all(Result::is_ok));
+    create.assert_async().await;
+    resume.assert_async().await;
+}
+
+#[test]
+fn test_invalid_default_header_is_reported_by_try_build() {
+    let error = Client::builder("test-key")
+        .default_header("X-Title", "line\nbreak")
+        .try_build()
+        .err()
+        .unwrap();
+
+    assert!(matches!(error, BuildError::InvalidHeader(ref message) if message.contains("X-Title")));
+
+    let error = StreamingClient::builder("test-key")
+        .default_header("Bad Name", "value")
+        .try_build()
+        .err()
+        .unwrap();
+
+    assert!(matches!(error, BuildError::InvalidHeader(ref message) if message.contains("Bad Name")));
+}
+
+#[test]
+#[should_panic(expected = "Invalid client configuration: Invalid header value: invalid value for header `X-Title`")]
+fn test_build_panics_with_the_configuration_error() {
+    Client::builder("test-key")
+        .default_header("X-Title", "line\nbreak")
+        .build();
+}
+
+#[tokio::test]
+async fn test_invalid_request_header_is_reported_without_sending() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v1/responses")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let error = client
+        .create_response_with_options(
+            CreateResponseBody::default(),
+            RequestOptions::new().header("Bad Name", "value"),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::InvalidHeader(_)));
+
+    let streaming = StreamingClient::with_base_url("test-key", server.url());
+    let error = streaming
+        .stream_response_with_options(
+            CreateResponseBody::default(),
+            RequestOptions::new().idempotency_key("bad\nkey"),
+        )
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(error, StreamingError::InvalidHeader(_)));
+
+    mock.assert_async().await;
+}
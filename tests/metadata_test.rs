@@ -0,0 +1,117 @@
+mod common;
+
+use common::{response_json, sse_body, text_message_events};
+use futures::StreamExt;
+use mockito::Server;
+use openresponses_rust::{Client, CreateResponseBody, RequestOptions, StreamingClient};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_create_response_with_metadata_exposes_headers() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/v1/responses")
+        .match_header("idempotency-key", "order-42")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("x-request-id", "req_123")
+        .with_header("openai-processing-ms", "42")
+        .with_header("x-ratelimit-limit-requests", "10000")
+        .with_header("x-ratelimit-remaining-requests", "9999")
+        .with_header("x-ratelimit-reset-requests", "6ms")
+        .with_header("x-ratelimit-limit-tokens", "30000")
+        .with_header("x-ratelimit-remaining-tokens", "29000")
+        .with_header("x-ratelimit-reset-tokens", "2s")
+        .with_body(response_json("resp_123", "completed").to_string())
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let response = client
+        .create_response_with_metadata(
+            CreateResponseBody::default(),
+            RequestOptions::new().idempotency_key("order-42"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.data.id, "resp_123");
+
+    let metadata = &response.metadata;
+    assert_eq!(metadata.status, 200);
+    assert_eq!(metadata.request_id.as_deref(), Some("req_123"));
+    assert_eq!(metadata.processing_time, Some(Duration::from_millis(42)));
+    assert_eq!(metadata.rate_limit.limit_requests, Some(10_000));
+    assert_eq!(metadata.rate_limit.remaining_requests, Some(9_999));
+    assert_eq!(metadata.rate_limit.reset_requests, Some(Duration::from_millis(6)));
+    assert_eq!(metadata.rate_limit.limit_tokens, Some(30_000));
+    assert_eq!(metadata.rate_limit.remaining_tokens, Some(29_000));
+    assert_eq!(metadata.rate_limit.reset_tokens, Some(Duration::from_secs(2)));
+    assert_eq!(metadata.headers["x-request-id"], "req_123");
+
+    assert_eq!(response.into_inner().id, "resp_123");
+}
+
+#[tokio::test]
+async fn test_metadata_fields_are_optional() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_123", "completed").to_string())
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let response = client
+        .create_response_with_metadata(CreateResponseBody::default(), RequestOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(response.metadata.request_id, None);
+    assert_eq!(response.metadata.processing_time, None);
+    assert_eq!(response.metadata.rate_limit, Default::default());
+}
+
+#[tokio::test]
+async fn test_get_response_and_streams_expose_metadata() {
+    let mut server = Server::new_async().await;
+    let get = server
+        .mock("GET", "/v1/responses/resp_123")
+        .match_query(mockito::Matcher::UrlEncoded("tenant".into(), "acme".into()))
+        .match_header("x-tenant", "acme")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("x-request-id", "req_get")
+        .with_body(response_json("resp_123", "completed").to_string())
+        .create_async()
+        .await;
+    let _stream = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_header("x-request-id", "req_stream")
+        .with_header("x-ratelimit-remaining-requests", "41")
+        .with_body(sse_body(&text_message_events("resp_123", &["Hi"]), true))
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let options = RequestOptions::new().header("X-Tenant", "acme").query("tenant", "acme");
+    let response = client.get_response_with_metadata("resp_123", options).await.unwrap();
+    assert_eq!(response.data.id, "resp_123");
+    assert_eq!(response.metadata.request_id.as_deref(), Some("req_get"));
+    get.assert_async().await;
+
+    let streaming = StreamingClient::with_base_url("test-key", server.url());
+    let stream = streaming
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap();
+    let metadata = stream.metadata().unwrap();
+    assert_eq!(metadata.status, 200);
+    assert_eq!(metadata.request_id.as_deref(), Some("req_stream"));
+    assert_eq!(metadata.rate_limit.remaining_requests, Some(41));
+    assert!(stream.collect::<Vec<_>>().await.iter().all(Result::is_ok));
+}
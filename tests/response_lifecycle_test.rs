@@ -0,0 +1,362 @@
+mod common;
+
+use common::{not_found_body, response_json};
+use futures::StreamExt;
+use mockito::{Matcher, Server};
+use openresponses_rust::{
+    CancellationToken, Client, ClientError, Item, ListInputItemsParams, ListOrder, PollOptions,
+    RequestOptions, ResponseStatus,
+};
+use serde_json::json;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_get_response() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/v1/responses/resp_123")
+        .match_header("authorization", "Bearer test-key")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_123", "in_progress").to_string())
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let response = client.get_response("resp_123").await.unwrap();
+
+    assert_eq!(response.id, "resp_123");
+    assert_eq!(response.status, ResponseStatus::InProgress);
+}
+
+#[tokio::test]
+async fn test_delete_response() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("DELETE", "/v1/responses/resp_123")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({"id": "resp_123", "object": "response.deleted", "deleted": true}).to_string(),
+        )
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let deleted = client.delete_response("resp_123").await.unwrap();
+
+    assert_eq!(deleted.id, "resp_123");
+    assert!(deleted.deleted);
+}
+
+#[tokio::test]
+async fn test_cancel_response() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/v1/responses/resp_123/cancel")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_123", "cancelled").to_string())
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let response = client.cancel_response("resp_123").await.unwrap();
+
+    assert_eq!(response.status, ResponseStatus::Cancelled);
+}
+
+#[tokio::test]
+async fn test_get_response_returns_structured_api_error() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/v1/responses/resp_missing")
+        .with_status(404)
+        .with_header("content-type", "application/json")
+        .with_body(not_found_body())
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let error = client.get_response("resp_missing").await.unwrap_err();
+
+    match error {
+        ClientError::ApiError {
+            status_code, error, ..
+        } => {
+            assert_eq!(status_code, 404);
+            assert_eq!(error.unwrap().code.as_deref(), Some("not_found"));
+        }
+        other => panic!("expected API error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_wait_for_completion_polls_until_terminal() {
+    let mut server = Server::new_async().await;
+    let pending = server
+        .mock("GET", "/v1/responses/resp_bg")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_bg", "queued").to_string())
+        .expect(2)
+        .create_async()
+        .await;
+    let completed = server
+        .mock("GET", "/v1/responses/resp_bg")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_bg", "completed").to_string())
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let response = client
+        .wait_for_completion(
+            "resp_bg",
+            PollOptions::new().interval(Duration::from_millis(10)),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, ResponseStatus::Completed);
+    pending.assert_async().await;
+    completed.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_wait_for_completion_stops_on_unknown_status() {
+    let mut server = Server::new_async().await;
+    let unknown = server
+        .mock("GET", "/v1/responses/resp_bg")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_bg", "paused").to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let response = client
+        .wait_for_completion("resp_bg", PollOptions::new().no_timeout())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, ResponseStatus::Other("paused".to_string()));
+    unknown.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_wait_for_completion_times_out() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/v1/responses/resp_bg")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_bg", "in_progress").to_string())
+        .expect_at_least(1)
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let error = client
+        .wait_for_completion(
+            "resp_bg",
+            PollOptions::new()
+                .interval(Duration::from_millis(10))
+                .timeout(Duration::from_millis(50)),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        ClientError::PollTimeout { ref response_id, last_status: ResponseStatus::InProgress }
+            if response_id == "resp_bg"
+    ));
+}
+
+#[tokio::test]
+async fn test_wait_for_completion_honors_cancel_token() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/v1/responses/resp_bg")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_bg", "in_progress").to_string())
+        .expect_at_least(1)
+        .create_async()
+        .await;
+
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        canceller.cancel();
+    });
+
+    let client = Client::with_base_url("test-key", server.url());
+    let error = client
+        .wait_for_completion(
+            "resp_bg",
+            PollOptions::new()
+                .interval(Duration::from_secs(5))
+                .cancel_token(token),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ClientError::Cancelled { .. }));
+}
+
+fn input_items_page(ids: &[&str], has_more: bool) -> String {
+    let data: Vec<_> = ids
+        .iter()
+        .map(|id| json!({"type": "message", "id": id, "role": "user", "content": [{"type": "input_text", "text": id}]}))
+        .collect();
+
+    json!({
+        "object": "list",
+        "data": data,
+        "first_id": ids.first(),
+        "last_id": ids.last(),
+        "has_more": has_more
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn test_list_input_items_sends_pagination_params() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/v1/responses/resp_123/input_items")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("after".into(), "msg_1".into()),
+            Matcher::UrlEncoded("limit".into(), "2".into()),
+            Matcher::UrlEncoded("order".into(), "asc".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(input_items_page(&["msg_2", "msg_3"], true))
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let page = client
+        .list_input_items(
+            "resp_123",
+            ListInputItemsParams::new()
+                .after("msg_1")
+                .limit(2)
+                .order(ListOrder::Asc),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(page.data.len(), 2);
+    assert_eq!(page.first_id.as_deref(), Some("msg_2"));
+    assert_eq!(page.last_id.as_deref(), Some("msg_3"));
+    assert!(page.has_more);
+}
+
+#[tokio::test]
+async fn test_list_input_items_stream_walks_all_pages() {
+    let mut server = Server::new_async().await;
+    let _first = server
+        .mock("GET", "/v1/responses/resp_123/input_items")
+        .match_query(Matcher::Exact("limit=2".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(input_items_page(&["msg_1", "msg_2"], true))
+        .create_async()
+        .await;
+    let _second = server
+        .mock("GET", "/v1/responses/resp_123/input_items")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("after".into(), "msg_2".into()),
+            Matcher::UrlEncoded("limit".into(), "2".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(input_items_page(&["msg_3"], false))
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let items: Vec<Item> = client
+        .list_input_items_stream("resp_123", ListInputItemsParams::new().limit(2))
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let ids: Vec<_> = items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Message { id, .. } => id.as_deref(),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(ids, ["msg_1", "msg_2", "msg_3"]);
+}
+
+#[tokio::test]
+async fn test_list_input_items_stream_applies_request_options_to_every_page() {
+    let mut server = Server::new_async().await;
+    let first = server
+        .mock("GET", "/v1/responses/resp_123/input_items")
+        .match_query(Matcher::Exact("limit=1&tenant=acme".into()))
+        .match_header("x-tenant", "acme")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(input_items_page(&["msg_1"], true))
+        .create_async()
+        .await;
+    let second = server
+        .mock("GET", "/v1/responses/resp_123/input_items")
+        .match_query(Matcher::Exact("after=msg_1&limit=1&tenant=acme".into()))
+        .match_header("x-tenant", "acme")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(input_items_page(&["msg_2"], false))
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let items: Vec<Item> = client
+        .list_input_items_stream_with_options(
+            "resp_123",
+            ListInputItemsParams::new().limit(1),
+            RequestOptions::new().header("X-Tenant", "acme").query("tenant", "acme"),
+        )
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    assert_eq!(items.len(), 2);
+    first.assert_async().await;
+    second.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_list_input_items_stream_surfaces_errors() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/v1/responses/resp_missing/input_items")
+        .with_status(404)
+        .with_header("content-type", "application/json")
+        .with_body(not_found_body())
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let results: Vec<_> = client
+        .list_input_items_stream("resp_missing", ListInputItemsParams::new())
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 1);
+    assert!(matches!(
+        results[0],
+        Err(ClientError::ApiError { status_code: 404, .. })
+    ));
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use common::{sse_body, text_message_events};
+use futures::StreamExt;
+use mockito::{Matcher, Server};
+use openresponses_rust::{CreateResponseBody, ResumeOptions, StreamingClient, StreamingEvent};
+use std::time::Duration;
+
+fn fast_resume() -> ResumeOptions {
+    ResumeOptions::new().reconnect_delay(Duration::from_millis(1))
+}
+
+#[tokio::test]
+async fn test_resumable_stream_reconnects_after_last_sequence_number() {
+    let mut server = Server::new_async().await;
+    let events = text_message_events("resp_123", &["Hel", "lo"]);
+    let (first, rest) = events.split_at(4);
+
+    let _create = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(first, false))
+        .create_async()
+        .await;
+    let resume = server
+        .mock("GET", "/v1/responses/resp_123")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("stream".into(), "true".into()),
+            Matcher::UrlEncoded("starting_after".into(), "4".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(rest, true))
+        .create_async()
+        .await;
+
+    let client = StreamingClient::with_base_url("test-key", server.url());
+    let stream = client
+        .stream_response_resumable(CreateResponseBody::default(), fast_resume())
+        .await
+        .unwrap();
+
+    let events: Vec<StreamingEvent> = stream.map(Result::unwrap).collect().await;
+    resume.assert_async().await;
+
+    let sequence_numbers: Vec<i32> = events.iter().filter_map(StreamingEvent::sequence_number).collect();
+    assert_eq!(sequence_numbers, (1..=9).collect::<Vec<_>>());
+
+    let text: String = events
+        .iter()
+        .filter_map(|event| match event {
+            StreamingEvent::OutputTextDelta { delta, .. } => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, "Hello");
+    assert!(matches!(events.last(), Some(StreamingEvent::Done)));
+}
+
+#[tokio::test]
+async fn test_unstored_stream_is_not_resumed() {
+    let mut server = Server::new_async().await;
+    let events = text_message_events("resp_123", &["Hel", "lo"]);
+
+    let _create = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(&events[..4], false))
+        .create_async()
+        .await;
+    let resume = server
+        .mock("GET", Matcher::Any)
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = StreamingClient::with_base_url("test-key", server.url());
+    let request = CreateResponseBody {
+        store: Some(false),
+        ..Default::default()
+    };
+    let stream = client
+        .stream_response_resumable(request, fast_resume())
+        .await
+        .unwrap();
+
+    let events: Vec<_> = stream.collect().await;
+    assert_eq!(events.len(), 4);
+    resume.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_completed_stream_is_not_resumed() {
+    let mut server = Server::new_async().await;
+    let events = text_message_events("resp_123", &["Hello"]);
+
+    let _create = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(&events, false))
+        .create_async()
+        .await;
+    let resume = server
+        .mock("GET", Matcher::Any)
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = StreamingClient::with_base_url("test-key", server.url());
+    let stream = client
+        .stream_response_resumable(CreateResponseBody::default(), fast_resume())
+        .await
+        .unwrap();
+
+    let events: Vec<_> = stream.collect().await;
+    assert!(events.iter().all(Result::is_ok));
+    resume.assert_async().await;
+}
@@ -0,0 +1,240 @@
+mod common;
+
+use common::{response_json, sse_body, text_message_events};
+use futures::StreamExt;
+use mockito::Server;
+use openresponses_rust::{
+    Client, ClientError, CreateResponseBody, ErrorType, RetryPolicy, StreamingClient,
+    StreamingError, StreamingEvent,
+};
+use std::time::Duration;
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy::new()
+        .base_delay(Duration::from_millis(1))
+        .max_delay(Duration::from_millis(5))
+}
+
+#[tokio::test]
+async fn test_client_retries_rate_limited_requests() {
+    let mut server = Server::new_async().await;
+    let limited = server
+        .mock("POST", "/v1/responses")
+        .with_status(429)
+        .with_header("retry-after-ms", "1")
+        .with_body(r#"{"error":{"message":"slow down","type":"too_many_requests"}}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let ok = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_123", "completed").to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = Client::builder("test-key")
+        .base_url(server.url())
+        .retry_policy(fast_policy())
+        .build();
+    let response = client
+        .create_response(CreateResponseBody::default())
+        .await
+        .unwrap();
+
+    assert_eq!(response.id, "resp_123");
+    limited.assert_async().await;
+    ok.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_client_gives_up_after_max_attempts() {
+    let mut server = Server::new_async().await;
+    let unavailable = server
+        .mock("POST", "/v1/responses")
+        .with_status(503)
+        .with_body("upstream unavailable")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = Client::builder("test-key")
+        .base_url(server.url())
+        .retry_policy(fast_policy().max_attempts(2))
+        .build();
+    let error = client
+        .create_response(CreateResponseBody::default())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ClientError::ApiError { status_code: 503, .. }));
+    unavailable.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_client_does_not_retry_by_default_or_on_client_errors() {
+    let mut server = Server::new_async().await;
+    let bad_request = server
+        .mock("POST", "/v1/responses")
+        .with_status(400)
+        .with_body(r#"{"error":{"message":"bad","type":"invalid_request"}}"#)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let default_client = Client::with_base_url("test-key", server.url());
+    assert!(default_client
+        .create_response(CreateResponseBody::default())
+        .await
+        .is_err());
+
+    let retrying_client = Client::builder("test-key")
+        .base_url(server.url())
+        .retry_policy(fast_policy())
+        .build();
+    assert!(retrying_client
+        .create_response(CreateResponseBody::default())
+        .await
+        .is_err());
+
+    bad_request.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_client_retries_on_configured_error_type() {
+    let mut server = Server::new_async().await;
+    let model_error = server
+        .mock("POST", "/v1/responses")
+        .with_status(400)
+        .with_body(r#"{"error":{"message":"model crashed","type":"model_error"}}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let _ok = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_123", "completed").to_string())
+        .create_async()
+        .await;
+
+    let client = Client::builder("test-key")
+        .base_url(server.url())
+        .retry_policy(fast_policy().retry_error_types(vec![ErrorType::ModelError]))
+        .build();
+
+    assert!(client.create_response(CreateResponseBody::default()).await.is_ok());
+    model_error.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_streaming_client_retries_before_first_event() {
+    let mut server = Server::new_async().await;
+    let failing = server
+        .mock("POST", "/v1/responses")
+        .with_status(500)
+        .with_body("boom")
+        .expect(1)
+        .create_async()
+        .await;
+    let empty = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body("")
+        .expect(1)
+        .create_async()
+        .await;
+    let _ok = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(&text_message_events("resp_123", &["Hi"]), true))
+        .create_async()
+        .await;
+
+    let client = StreamingClient::builder("test-key")
+        .base_url(server.url())
+        .retry_policy(fast_policy().retry_sent_requests(true))
+        .build();
+    let events: Vec<_> = client
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    failing.assert_async().await;
+    empty.assert_async().await;
+    assert!(events.iter().all(Result::is_ok));
+    assert!(matches!(events.last(), Some(Ok(StreamingEvent::Done))));
+}
+
+#[tokio::test]
+async fn test_streaming_client_reports_api_error_after_retries() {
+    let mut server = Server::new_async().await;
+    let limited = server
+        .mock("POST", "/v1/responses")
+        .with_status(429)
+        .with_body(r#"{"error":{"message":"slow down","type":"too_many_requests"}}"#)
+        .expect(3)
+        .create_async()
+        .await;
+
+    let client = StreamingClient::builder("test-key")
+        .base_url(server.url())
+        .retry_policy(fast_policy())
+        .build();
+    let error = match client.stream_response(CreateResponseBody::default()).await {
+        Ok(_) => panic!("expected API error"),
+        Err(error) => error,
+    };
+
+    assert!(matches!(error, StreamingError::ApiError { status_code: 429, .. }));
+    limited.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_sent_requests_are_not_retried_unless_enabled() {
+    let mut server = Server::new_async().await;
+    let empty = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body("")
+        .expect(1)
+        .create_async()
+        .await;
+    let conflict = server
+        .mock("POST", "/v1/responses")
+        .with_status(409)
+        .with_body(r#"{"error":{"message":"conflict","type":"invalid_request"}}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = StreamingClient::builder("test-key")
+        .base_url(server.url())
+        .retry_policy(fast_policy())
+        .build();
+    let events: Vec<_> = client
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert!(events.is_empty());
+    empty.assert_async().await;
+
+    let error = Client::builder("test-key")
+        .base_url(server.url())
+        .retry_policy(fast_policy())
+        .build()
+        .create_response(CreateResponseBody::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::ApiError { status_code: 409, .. }));
+    conflict.assert_async().await;
+}
@@ -0,0 +1,282 @@
+#![cfg(feature = "testing")]
+
+use openresponses_rust::testing::{self, MockReply, MockServer};
+use futures::StreamExt;
+use openresponses_rust::{
+    CancellationToken, ConversationMode, CreateResponseBody, ExecuteOptions, Input,
+    InputTokensDetails, Item, OutputTokensDetails, ResponseResource, ResponsesClient,
+    RetryPolicy, RunEvent, RunnerError, Runner, StreamingEvent, Tool, ToolRegistry, Usage,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[derive(Deserialize)]
+struct WeatherArgs {
+    city: String,
+}
+
+fn runner(server: &MockServer) -> Runner {
+    let client = ResponsesClient::builder("test-key")
+        .base_url(server.url())
+        .retry_policy(RetryPolicy::none())
+        .build();
+    let registry = ToolRegistry::new().register(
+        Tool::function("get_weather"),
+        |args: WeatherArgs| async move { Ok::<_, String>(format!("Sunny in {}", args.city)) },
+    );
+    Runner::new(client, registry)
+}
+
+fn request() -> CreateResponseBody {
+    CreateResponseBody {
+        model: Some("mock-model".to_string()),
+        input: Some(Input::Single("Weather in Seoul and Busan?".to_string())),
+        ..Default::default()
+    }
+}
+
+fn with_usage(mut response: ResponseResource, input_tokens: i32, output_tokens: i32) -> ResponseResource {
+    response.usage = Some(Usage {
+        input_tokens,
+        output_tokens,
+        total_tokens: input_tokens + output_tokens,
+        input_tokens_details: InputTokensDetails { cached_tokens: 0 },
+        output_tokens_details: OutputTokensDetails { reasoning_tokens: 0 },
+    });
+    response
+}
+
+fn tool_turn(id: &str) -> MockReply {
+    MockReply::response(with_usage(
+        testing::response(
+            id,
+            vec![
+                testing::function_call("call_1", "get_weather", r#"{"city":"Seoul"}"#),
+                testing::function_call("call_2", "get_weather", r#"{"city":"Busan"}"#),
+            ],
+        ),
+        10,
+        4,
+    ))
+}
+
+fn answer_turn(id: &str) -> MockReply {
+    MockReply::response(with_usage(
+        testing::response(id, vec![Item::assistant_message("Sunny in both.")]),
+        30,
+        6,
+    ))
+}
+
+fn input_items(request: &CreateResponseBody) -> &[Item] {
+    match &request.input {
+        Some(Input::Items(items)) => items,
+        other => panic!("expected input items, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_stateless_run_replays_the_transcript() {
+    let server = MockServer::start().await;
+    server.enqueue(tool_turn("resp_1")).enqueue(answer_turn("resp_2"));
+
+    let result = runner(&server).run(request()).await.unwrap();
+
+    assert_eq!(result.output_text(), "Sunny in both.");
+    assert_eq!(result.responses.len(), 2);
+    assert_eq!(result.tool_calls, 2);
+    let usage = result.usage.unwrap();
+    assert_eq!((usage.input_tokens, usage.output_tokens, usage.total_tokens), (40, 10, 50));
+    // user message, two calls, two outputs, final answer
+    assert_eq!(result.transcript.len(), 6);
+    assert!(matches!(
+        &result.transcript[4],
+        Item::FunctionCallOutput { call_id, .. } if call_id == "call_2"
+    ));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].tools.as_ref().map(Vec::len), Some(1));
+    assert_eq!(input_items(&requests[1]), &result.transcript[..5]);
+    assert_eq!(requests[1].previous_response_id, None);
+}
+
+#[tokio::test]
+async fn test_stateful_run_chains_previous_response_id() {
+    let server = MockServer::start().await;
+    server.enqueue(tool_turn("resp_1")).enqueue(answer_turn("resp_2"));
+
+    let result = runner(&server)
+        .mode(ConversationMode::Stateful)
+        .run(request())
+        .await
+        .unwrap();
+    assert_eq!(result.final_response().unwrap().id, "resp_2");
+
+    let requests = server.requests();
+    assert_eq!(requests[1].previous_response_id.as_deref(), Some("resp_1"));
+    let items = input_items(&requests[1]);
+    assert_eq!(items.len(), 2);
+    assert!(items.iter().all(|item| matches!(item, Item::FunctionCallOutput { .. })));
+}
+
+#[tokio::test]
+async fn test_runs_are_bounded_by_turns_and_tool_calls() {
+    let server = MockServer::start().await;
+    server.enqueue(tool_turn("resp_1"));
+    let error = runner(&server).max_turns(1).run(request()).await.unwrap_err();
+    assert!(matches!(
+        error,
+        RunnerError::MaxTurns { max_turns: 1, ref result } if result.responses.len() == 1 && result.tool_calls == 0
+    ));
+
+    server.enqueue(tool_turn("resp_2"));
+    let error = runner(&server).max_tool_calls(1).run(request()).await.unwrap_err();
+    assert!(matches!(
+        error,
+        RunnerError::MaxToolCalls { max_tool_calls: 1, ref result } if result.transcript.len() == 3
+    ));
+    assert_eq!(server.pending_replies(), 0);
+
+    // The request's own `max_tool_calls` bounds the whole run.
+    server.enqueue(tool_turn("resp_3"));
+    let limited = CreateResponseBody {
+        max_tool_calls: Some(1),
+        ..request()
+    };
+    let error = runner(&server).run(limited).await.unwrap_err();
+    assert!(matches!(error, RunnerError::MaxToolCalls { max_tool_calls: 1, .. }));
+
+    server.enqueue(tool_turn("resp_4")).enqueue(answer_turn("resp_5"));
+    let budget = CreateResponseBody {
+        max_tool_calls: Some(3),
+        ..request()
+    };
+    runner(&server).run(budget.clone()).await.unwrap();
+    let requests = server.requests();
+    assert_eq!(requests[requests.len() - 1].max_tool_calls, Some(1));
+
+    // With a runner limit, the request's value stays a per-response cap.
+    server.enqueue(tool_turn("resp_6")).enqueue(answer_turn("resp_7"));
+    runner(&server).max_tool_calls(10).run(budget).await.unwrap();
+    let requests = server.requests();
+    assert_eq!(requests[requests.len() - 1].max_tool_calls, Some(3));
+}
+
+#[tokio::test]
+async fn test_tool_calls_run_in_parallel_unless_disabled() {
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let registry = {
+        let (running, peak) = (running.clone(), peak.clone());
+        ToolRegistry::new().register(Tool::function("get_weather"), move |args: WeatherArgs| {
+            let (running, peak) = (running.clone(), peak.clone());
+            async move {
+                peak.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok::<_, String>(format!("Sunny in {}", args.city))
+            }
+        })
+    };
+    let server = MockServer::start().await;
+    let client = ResponsesClient::builder("test-key")
+        .base_url(server.url())
+        .retry_policy(RetryPolicy::none())
+        .build();
+    let runner = Runner::new(client, registry);
+
+    for (parallel_tool_calls, expected_peak) in [(None, 2), (Some(false), 1)] {
+        server.enqueue(tool_turn("resp_1")).enqueue(answer_turn("resp_2"));
+        peak.store(0, Ordering::SeqCst);
+        let result = runner
+            .run(CreateResponseBody {
+                parallel_tool_calls,
+                ..request()
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.tool_calls, 2);
+        assert_eq!(peak.load(Ordering::SeqCst), expected_peak);
+    }
+
+    let token = CancellationToken::new();
+    token.cancel();
+    server.enqueue(tool_turn("resp_3"));
+    let error = runner
+        .execute_options(ExecuteOptions::new().cancel_token(token))
+        .run(request())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        RunnerError::Cancelled { ref result } if result.transcript.len() == 5
+    ));
+}
+
+#[tokio::test]
+async fn test_streamed_run_interleaves_turns_and_tool_calls() {
+    let server = MockServer::start().await;
+    server.enqueue(tool_turn("resp_1")).enqueue(answer_turn("resp_2"));
+
+    let events: Vec<_> = runner(&server)
+        .run_streamed(request())
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let outline: Vec<String> = events
+        .iter()
+        .filter_map(|event| match event {
+            RunEvent::TurnStarted { turn } => Some(format!("start {turn}")),
+            RunEvent::TurnFinished { turn, response } => Some(format!("finish {turn} {}", response.id)),
+            RunEvent::ToolCallStarted { turn, call: Item::FunctionCall { call_id, .. } } => {
+                Some(format!("call {turn} {call_id}"))
+            }
+            RunEvent::ToolCallFinished { turn, output: Item::FunctionCallOutput { call_id, .. } } => {
+                Some(format!("output {turn} {call_id}"))
+            }
+            RunEvent::Finished(result) => Some(format!("done {}", result.tool_calls)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        outline,
+        [
+            "start 0",
+            "finish 0 resp_1",
+            "call 0 call_1",
+            "call 0 call_2",
+            "output 0 call_1",
+            "output 0 call_2",
+            "start 1",
+            "finish 1 resp_2",
+            "done 2",
+        ]
+    );
+
+    let text: String = events
+        .iter()
+        .filter_map(|event| match event {
+            RunEvent::Event {
+                turn: 1,
+                event: StreamingEvent::OutputTextDelta { delta, .. },
+            } => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, "Sunny in both.");
+    let Some(RunEvent::Finished(result)) = events.last() else {
+        panic!("run did not finish");
+    };
+    assert_eq!(result.transcript.len(), 6);
+    assert_eq!(server.requests()[1].stream, Some(true));
+
+    server.enqueue(MockReply::raw_sse(
+        "data: {\"type\":\"response.created\",\"sequence_number\":0}\n\n",
+    ));
+    let last = runner(&server).run_streamed(request()).collect::<Vec<_>>().await.pop();
+    assert!(matches!(last, Some(Err(RunnerError::Stream(_)))));
+}
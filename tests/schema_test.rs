@@ -0,0 +1,158 @@
+use openresponses_rust::{ArgumentsError, Item, strict_schema};
+use serde::Deserialize;
+use serde_json::json;
+
+#[test]
+fn test_strict_schema_closes_objects_and_drops_unsupported_keywords() {
+    let schema = strict_schema(json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Search",
+        "type": "object",
+        "properties": {
+            "title": {"type": "string", "default": "", "format": "uint32"},
+            "when": {"type": ["string", "null"], "format": "date-time"},
+            "filter": {
+                "oneOf": [
+                    {"type": "object", "properties": {"tag": {"type": "string"}}},
+                    {"type": "null"}
+                ]
+            },
+            "labels": {"type": "object", "additionalProperties": {"type": "string"}}
+        },
+        "required": ["title"]
+    }));
+
+    assert_eq!(
+        schema,
+        json!({
+            "type": "object",
+            "properties": {
+                "title": {"type": "string"},
+                "when": {"type": ["string", "null"], "format": "date-time"},
+                "filter": {
+                    "anyOf": [
+                        {
+                            "type": "object",
+                            "properties": {"tag": {"type": ["string", "null"]}},
+                            "required": ["tag"],
+                            "additionalProperties": false
+                        },
+                        {"type": "null"}
+                    ]
+                },
+                "labels": {"type": ["object", "null"], "additionalProperties": {"type": "string"}}
+            },
+            "required": ["filter", "labels", "title", "when"],
+            "additionalProperties": false
+        })
+    );
+}
+
+#[test]
+fn test_strict_schema_keeps_optional_properties_optional() {
+    let schema = strict_schema(json!({
+        "type": "object",
+        "properties": {
+            "a": {"type": "string"},
+            "b": {"type": "integer"},
+            "unit": {"type": "string", "enum": ["c", "f"]},
+            "point": {"$ref": "#/$defs/Point"}
+        },
+        "required": ["b"]
+    }));
+
+    assert_eq!(schema["required"], json!(["a", "b", "point", "unit"]));
+    assert_eq!(schema["properties"]["a"], json!({"type": ["string", "null"]}));
+    assert_eq!(schema["properties"]["b"], json!({"type": "integer"}));
+    assert_eq!(
+        schema["properties"]["unit"],
+        json!({"type": ["string", "null"], "enum": ["c", "f", null]})
+    );
+    assert_eq!(
+        schema["properties"]["point"],
+        json!({"anyOf": [{"$ref": "#/$defs/Point"}, {"type": "null"}]})
+    );
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+struct SearchArgs {
+    query: String,
+    limit: Option<u32>,
+}
+
+#[test]
+fn test_function_call_arguments_parse_into_typed_values() {
+    let call = Item::FunctionCall {
+        id: None,
+        call_id: "call_1".to_string(),
+        name: "search".to_string(),
+        arguments: r#"{"query": "rust", "limit": null}"#.to_string(),
+        status: None,
+    };
+    assert_eq!(
+        call.parse_arguments::<SearchArgs>().unwrap(),
+        SearchArgs {
+            query: "rust".to_string(),
+            limit: None,
+        }
+    );
+    assert!(matches!(
+        Item::user_message("hi").parse_arguments::<SearchArgs>(),
+        Err(ArgumentsError::NotAFunctionCall)
+    ));
+
+    let malformed = Item::FunctionCall {
+        id: None,
+        call_id: "call_2".to_string(),
+        name: "search".to_string(),
+        arguments: r#"{"limit": 3}"#.to_string(),
+        status: None,
+    };
+    assert!(matches!(
+        malformed.parse_arguments::<SearchArgs>(),
+        Err(ArgumentsError::Invalid(_))
+    ));
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn test_function_for_derives_strict_parameters() {
+    use openresponses_rust::Tool;
+
+    /// Searches the docs.
+    #[derive(schemars::JsonSchema)]
+    #[allow(dead_code)]
+    struct Search {
+        query: String,
+        limit: Option<u32>,
+        scope: Scope,
+    }
+
+    #[derive(schemars::JsonSchema)]
+    #[serde(rename_all = "lowercase")]
+    #[allow(dead_code)]
+    enum Scope {
+        Api,
+        Guides,
+    }
+
+    let Tool::Function {
+        name,
+        description,
+        parameters: Some(parameters),
+        strict,
+    } = Tool::function_for::<Search>("search")
+    else {
+        panic!("expected a function tool with parameters");
+    };
+    assert_eq!(name, "search");
+    assert_eq!(description.as_deref(), Some("Searches the docs."));
+    assert_eq!(strict, Some(true));
+    assert_eq!(parameters["additionalProperties"], json!(false));
+    assert_eq!(parameters["required"], json!(["limit", "query", "scope"]));
+    assert_eq!(parameters["properties"]["limit"]["type"], json!(["integer", "null"]));
+    assert_eq!(parameters["properties"]["scope"]["enum"], json!(["api", "guides"]));
+    assert!(parameters.get("$schema").is_none() && parameters.get("title").is_none());
+    assert!(parameters["properties"]["limit"].get("format").is_none());
+}
@@ -0,0 +1,192 @@
+#![cfg(feature = "server")]
+
+mod common;
+
+use common::{response_json, text_message_events};
+use futures::StreamExt;
+use openresponses_rust::server::{self, ProviderError, ProviderResponse, ResponsesProvider};
+use openresponses_rust::{
+    ClientError, CreateResponseBody, Item, MessageContent, ResponseResource, ResponseStatus,
+    ResponseStreamExt, ResponsesClient, StreamingError, StreamingEvent,
+};
+
+struct TestProvider;
+
+#[async_trait::async_trait]
+impl ResponsesProvider for TestProvider {
+    async fn create_response(
+        &self,
+        request: CreateResponseBody,
+    ) -> Result<ProviderResponse, ProviderError> {
+        match request.model.as_deref() {
+            Some("streaming-model") => {
+                let events: Vec<StreamingEvent> = text_message_events("resp_stream", &["Hel", "lo"])
+                    .into_iter()
+                    .map(|value| serde_json::from_value(value).unwrap())
+                    .collect();
+                Ok(ProviderResponse::stream(futures::stream::iter(events)))
+            }
+            Some("json-model") => {
+                let mut response: ResponseResource =
+                    serde_json::from_value(response_json("resp_json", "completed")).unwrap();
+                response.output = vec![Item::assistant_message("Hello from JSON")];
+                Ok(response.into())
+            }
+            Some("queued-model") => {
+                let response: ResponseResource =
+                    serde_json::from_value(response_json("resp_queued", "queued")).unwrap();
+                Ok(response.into())
+            }
+            _ => Err(ProviderError::not_found("Unknown model").param("model")),
+        }
+    }
+}
+
+async fn start_server() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(server::serve(listener, TestProvider));
+    format!("http://{address}")
+}
+
+fn request(model: &str) -> CreateResponseBody {
+    CreateResponseBody {
+        model: Some(model.to_string()),
+        ..Default::default()
+    }
+}
+
+fn message_text(response: &ResponseResource) -> &str {
+    match &response.output[0] {
+        Item::Message { content, .. } => match content.as_slice() {
+            [MessageContent::OutputText { text, .. }] => text,
+            other => panic!("unexpected content {other:?}"),
+        },
+        other => panic!("expected message, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_server_streams_provider_events() {
+    let client = ResponsesClient::with_base_url("test-key", start_server().await);
+
+    let events: Vec<_> = client
+        .stream_response(request("streaming-model"))
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    assert_eq!(events[0].sequence_number(), Some(0));
+    assert!(matches!(events.last(), Some(StreamingEvent::Done)));
+    let completed = events
+        .iter()
+        .find_map(|event| match event {
+            StreamingEvent::ResponseCompleted { response, .. } => Some(response),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(message_text(completed), "Hello");
+}
+
+#[tokio::test]
+async fn test_server_converts_between_json_and_streams() {
+    let client = ResponsesClient::with_base_url("test-key", start_server().await);
+
+    let collected = client.create_response(request("streaming-model")).await.unwrap();
+    assert_eq!(collected.id, "resp_stream");
+    assert_eq!(message_text(&collected), "Hello");
+
+    let json = client.create_response(request("json-model")).await.unwrap();
+    assert_eq!(json.status, ResponseStatus::Completed);
+
+    let events: Vec<_> = client
+        .stream_response(request("json-model"))
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert!(matches!(events.first(), Some(StreamingEvent::ResponseCreated { .. })));
+    assert!(matches!(
+        &events[events.len() - 2],
+        StreamingEvent::ResponseCompleted { response, .. } if response.id == "resp_json"
+    ));
+}
+
+#[tokio::test]
+async fn test_server_streams_json_responses_item_by_item() {
+    let client = ResponsesClient::with_base_url("test-key", start_server().await);
+
+    let mut stream = client
+        .stream_response(request("json-model"))
+        .await
+        .unwrap()
+        .accumulate();
+    let mut text = String::new();
+    while let Some(event) = stream.next().await {
+        if let StreamingEvent::OutputTextDelta { delta, .. } = event.unwrap().event {
+            text.push_str(&delta);
+        }
+    }
+    assert_eq!(text, "Hello from JSON");
+    let accumulator = stream.into_accumulator();
+    assert!(accumulator.is_finished());
+    assert_eq!(message_text(accumulator.snapshot().unwrap()), "Hello from JSON");
+
+    let error = client.stream_response(request("queued-model")).await.err().unwrap();
+    assert!(matches!(error, StreamingError::ApiError { status_code: 500, .. }));
+}
+
+#[tokio::test]
+async fn test_server_reports_errors_as_api_error_bodies() {
+    let base_url = start_server().await;
+    let client = ResponsesClient::with_base_url("test-key", &base_url);
+
+    let error = client.create_response(request("missing")).await.unwrap_err();
+    assert!(matches!(
+        error,
+        ClientError::ApiError { status_code: 404, error: Some(ref detail), .. }
+            if detail.message == "Unknown model" && detail.param.as_deref() == Some("model")
+    ));
+
+    let http = reqwest::Client::new();
+    let invalid = http
+        .post(format!("{base_url}/v1/responses"))
+        .body("{not json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(invalid.status(), 400);
+    let body: serde_json::Value = invalid.json().await.unwrap();
+    assert_eq!(body["error"]["type"], "invalid_request");
+
+    let not_acceptable = http
+        .post(format!("{base_url}/v1/responses"))
+        .header("accept", "text/event-stream")
+        .json(&request("json-model"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(not_acceptable.status(), 406);
+
+    let streaming = CreateResponseBody {
+        stream: Some(true),
+        ..request("streaming-model")
+    };
+    for (accept, status) in [
+        ("text/event-stream;q=0, application/json", 406),
+        ("text/event-stream; q=0.0, */*", 406),
+        ("application/json, text/event-stream;q=0.5", 200),
+    ] {
+        let response = http
+            .post(format!("{base_url}/v1/responses"))
+            .header("accept", accept)
+            .json(&streaming)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), status, "{accept}");
+    }
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use common::text_message_events;
+use futures::StreamExt;
+use openresponses_rust::sse::{encode_stream, parse_bytes};
+use openresponses_rust::{SseEncoder, StreamingError, StreamingEvent};
+use serde_json::json;
+
+fn unnumbered_events() -> Vec<StreamingEvent> {
+    text_message_events("resp_123", &["Hel", "lo"])
+        .into_iter()
+        .map(|mut value| {
+            value["sequence_number"] = json!(0);
+            serde_json::from_value(value).unwrap()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_encoded_stream_round_trips_through_the_parser() {
+    let events = unnumbered_events();
+    let count = events.len();
+
+    let frames: Vec<_> = encode_stream(futures::stream::iter(events.clone()))
+        .collect()
+        .await;
+    assert_eq!(frames.len(), count + 1);
+    assert_eq!(frames.last().unwrap().as_ref().unwrap().as_ref(), b"data: [DONE]\n\n");
+    assert!(frames[0]
+        .as_ref()
+        .unwrap()
+        .starts_with(b"event: response.created\ndata: {"));
+
+    let parsed: Vec<_> = parse_bytes(futures::stream::iter(frames))
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    let sequence_numbers: Vec<_> = parsed.iter().map(StreamingEvent::sequence_number).collect();
+    let mut expected: Vec<_> = (0..count as i32).map(Some).collect();
+    expected.push(None);
+    assert_eq!(sequence_numbers, expected);
+    assert!(matches!(parsed.last(), Some(StreamingEvent::Done)));
+}
+
+#[test]
+fn test_encoder_numbers_unknown_events_and_rejects_invalid_sequences() {
+    let mut encoder = SseEncoder::new().starting_at(41);
+    let extension: StreamingEvent =
+        serde_json::from_value(json!({"type": "acme:trace_event", "phase": "start"})).unwrap();
+    let frame = encoder.encode(extension).unwrap();
+    assert_eq!(
+        frame.as_ref(),
+        b"event: acme:trace_event\ndata: {\"phase\":\"start\",\"sequence_number\":41,\"type\":\"acme:trace_event\"}\n\n"
+    );
+    assert_eq!(encoder.next_sequence_number(), 42);
+
+    let orphan_delta = unnumbered_events().remove(3);
+    assert!(matches!(
+        encoder.encode(orphan_delta),
+        Err(StreamingError::LifecycleError { .. })
+    ));
+}
+
+#[test]
+fn test_encoder_is_finished_after_done_or_an_error() {
+    let mut encoder = SseEncoder::new();
+    encoder.done().unwrap();
+    assert!(encoder.is_finished());
+    let error = encoder.done().unwrap_err();
+    assert!(matches!(
+        error,
+        StreamingError::LifecycleError { ref message } if message.contains("after `[DONE]`")
+    ));
+    assert!(matches!(
+        encoder.encode(StreamingEvent::Done),
+        Err(StreamingError::LifecycleError { .. })
+    ));
+
+    let mut events = unnumbered_events();
+    let mut encoder = SseEncoder::new();
+    assert!(encoder.encode(events.remove(3)).is_err());
+    assert!(encoder.is_finished());
+    // Even a valid opening event is refused once the stream has failed.
+    let error = encoder.encode(events.remove(0)).unwrap_err();
+    assert!(matches!(
+        error,
+        StreamingError::LifecycleError { ref message } if message.contains("after an earlier error")
+    ));
+    assert!(encoder.done().is_err());
+    assert_eq!(encoder.next_sequence_number(), 0);
+}
+
+#[test]
+fn test_encoder_rejects_events_past_the_last_sequence_number() {
+    let extension = || -> StreamingEvent {
+        serde_json::from_value(json!({"type": "acme:trace_event"})).unwrap()
+    };
+    let mut encoder = SseEncoder::new().starting_at(i32::MAX);
+    let frame = encoder.encode(extension()).unwrap();
+    assert!(String::from_utf8_lossy(&frame).contains(&format!("\"sequence_number\":{}", i32::MAX)));
+
+    assert!(matches!(
+        encoder.encode(extension()),
+        Err(StreamingError::LifecycleError { .. })
+    ));
+    assert!(encoder.is_finished());
+    assert!(encoder.done().is_err());
+}
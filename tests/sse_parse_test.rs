@@ -0,0 +1,77 @@
+mod common;
+
+use bytes::Bytes;
+use common::{sse_body, text_message_events};
+use futures::StreamExt;
+use openresponses_rust::sse::{parse_bytes, parse_reader, parse_str};
+use openresponses_rust::{SseParser, StreamingError, StreamingEvent, ValidationMode};
+
+fn expected_events() -> Vec<StreamingEvent> {
+    let mut events: Vec<StreamingEvent> = text_message_events("resp_123", &["Hél", "lo"])
+        .into_iter()
+        .map(|value| serde_json::from_value(value).unwrap())
+        .collect();
+    events.push(StreamingEvent::Done);
+    events
+}
+
+fn body() -> String {
+    sse_body(&text_message_events("resp_123", &["Hél", "lo"]), true)
+}
+
+#[tokio::test]
+async fn test_parse_str_yields_validated_events() {
+    let events: Vec<_> = parse_str(&body()).map(Result::unwrap).collect().await;
+    assert_eq!(events, expected_events());
+}
+
+#[tokio::test]
+async fn test_parse_bytes_handles_arbitrary_chunk_boundaries() {
+    let body = body().into_bytes();
+    let chunks: Vec<Result<Bytes, std::io::Error>> = body
+        .chunks(7)
+        .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+        .collect();
+
+    let events: Vec<_> = parse_bytes(futures::stream::iter(chunks))
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(events, expected_events());
+
+    let failing = futures::stream::iter(vec![
+        Ok(Bytes::from(body[..20].to_vec())),
+        Err(std::io::Error::other("relay disconnected")),
+    ]);
+    let results: Vec<_> = parse_bytes(failing).collect().await;
+    assert!(matches!(
+        results.last(),
+        Some(Err(StreamingError::StreamError(message))) if message.contains("relay disconnected")
+    ));
+}
+
+#[tokio::test]
+async fn test_parse_reader_reads_log_files() {
+    let path = std::env::temp_dir().join(format!("openresponses-{}-stream.log", std::process::id()));
+    std::fs::write(&path, body()).unwrap();
+
+    let file = tokio::fs::File::open(&path).await.unwrap();
+    let events: Vec<_> = parse_reader(file).map(Result::unwrap).collect().await;
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(events, expected_events());
+}
+
+#[tokio::test]
+async fn test_sse_parser_applies_validation_mode() {
+    let body = "data: {\"type\":\"acme:trace_event\",\"sequence_number\":1}\n\n";
+
+    let strict: Vec<_> = parse_str(body).collect().await;
+    assert!(matches!(strict[0], Err(StreamingError::MissingEventType { .. })));
+
+    let mut warn = SseParser::new()
+        .validation_mode(ValidationMode::Warn)
+        .parse_str(body);
+    assert!(matches!(warn.next().await, Some(Ok(StreamingEvent::Unknown { .. }))));
+    assert_eq!(warn.violations().len(), 1);
+}
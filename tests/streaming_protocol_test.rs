@@ -245,3 +245,38 @@ async fn test_raw_sse_stream_preserves_event_name() {
         }
     );
 }
+
+#[tokio::test]
+async fn test_skipped_sequence_number_is_rejected() {
+    let mut server = Server::new_async().await;
+    let body = concat!(
+        "event: acme:trace_event\n",
+        "data: {\"type\":\"acme:trace_event\",\"sequence_number\":1}\n\n",
+        "event: acme:trace_event\n",
+        "data: {\"type\":\"acme:trace_event\",\"sequence_number\":4}\n\n"
+    );
+
+    let _mock = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let client = StreamingClient::with_base_url("test-key", server.url());
+    let mut stream = client
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap();
+
+    stream.next().await.unwrap().unwrap();
+    assert_eq!(stream.last_sequence_number(), Some(1));
+
+    let error = stream.next().await.unwrap().unwrap_err();
+    assert!(matches!(
+        error,
+        StreamingError::SequenceError { previous: 1, received: 4 }
+    ));
+    assert_eq!(error.to_string(), "Sequence numbers 2..4 were skipped after 1");
+}
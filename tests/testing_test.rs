@@ -0,0 +1,201 @@
+#![cfg(feature = "testing")]
+
+use futures::StreamExt;
+use openresponses_rust::server::ProviderError;
+use openresponses_rust::testing::{self, MockReply, MockServer};
+use openresponses_rust::{
+    ClientError, CreateResponseBody, Item, MessageContent, PollOptions, ResponseStatus,
+    ResponsesClient, ResumeOptions, RetryPolicy, StreamAccumulator, StreamingError,
+    StreamingEvent,
+};
+use std::time::Duration;
+
+fn request(model: &str) -> CreateResponseBody {
+    CreateResponseBody {
+        model: Some(model.to_string()),
+        ..Default::default()
+    }
+}
+
+fn client(server: &MockServer, retry_policy: RetryPolicy) -> ResponsesClient {
+    ResponsesClient::builder("test-key")
+        .base_url(server.url())
+        .retry_policy(retry_policy)
+        .build()
+}
+
+#[tokio::test]
+async fn test_generated_stream_assembles_into_the_target_response() {
+    let target = testing::response(
+        "resp_mock",
+        vec![
+            testing::reasoning("Looking up the weather"),
+            testing::function_call("call_1", "get_weather", r#"{"city": "Seoul"}"#),
+            Item::assistant_message("It is sunny in Seoul."),
+        ],
+    );
+    let server = MockServer::start().await;
+    server.enqueue(MockReply::response(target.clone()));
+
+    let mut stream = client(&server, RetryPolicy::none())
+        .stream_response(request("mock-model"))
+        .await
+        .unwrap();
+    let mut accumulator = StreamAccumulator::new();
+    let mut completed = None;
+    let mut text_deltas = 0;
+    while let Some(event) = stream.next().await {
+        let event = event.unwrap();
+        if matches!(event, StreamingEvent::OutputTextDelta { .. }) {
+            text_deltas += 1;
+        }
+        if let Some(response) = accumulator.apply(&event).unwrap() {
+            completed = Some(response.clone());
+        }
+    }
+
+    let completed = completed.unwrap();
+    assert_eq!(text_deltas, 5);
+    assert_eq!(completed.output.len(), 3);
+    assert!(matches!(
+        &completed.output[2],
+        Item::Message { id: Some(id), content, .. }
+            if id == "item_2" && content == &vec![MessageContent::output_text("It is sunny in Seoul.")]
+    ));
+    assert!(stream.violations().is_empty());
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].model.as_deref(), Some("mock-model"));
+    assert_eq!(requests[0].stream, Some(true));
+    assert_eq!(server.pending_replies(), 0);
+}
+
+#[tokio::test]
+async fn test_replies_are_served_in_order_and_retries_honour_rate_limits() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockReply::rate_limited(Duration::from_millis(5)))
+        .enqueue(MockReply::response(testing::response("resp_json", vec![])))
+        .enqueue(MockReply::error(
+            ProviderError::invalid_request("Unsupported model").param("model"),
+        ));
+    let client = client(&server, RetryPolicy::new().max_attempts(2));
+
+    let response = client.create_response(request("first")).await.unwrap();
+    assert_eq!(response.id, "resp_json");
+
+    let error = client.create_response(request("second")).await.unwrap_err();
+    assert!(matches!(
+        error,
+        ClientError::ApiError { status_code: 400, error: Some(ref detail), .. }
+            if detail.param.as_deref() == Some("model")
+    ));
+
+    let models: Vec<_> = server
+        .requests()
+        .into_iter()
+        .map(|request| request.model.unwrap())
+        .collect();
+    assert_eq!(models, ["first", "first", "second"]);
+
+    let empty = client.create_response(request("third")).await.unwrap_err();
+    assert!(matches!(empty, ClientError::ApiError { status_code: 500, .. }));
+}
+
+#[tokio::test]
+async fn test_disconnects_and_raw_streams() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(
+            MockReply::response(testing::response(
+                "resp_cut",
+                vec![Item::assistant_message("never finished")],
+            ))
+            .disconnect_after(3),
+        )
+        .enqueue(MockReply::raw_sse(
+            "data: {\"type\":\"response.created\",\"sequence_number\":0}\n\n",
+        ));
+    let client = client(&server, RetryPolicy::none());
+
+    let events: Vec<_> = client
+        .stream_response(request("mock-model"))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert_eq!(events.len(), 4);
+    assert!(events[..3].iter().all(Result::is_ok));
+    assert!(matches!(events[3], Err(StreamingError::StreamError(_))));
+
+    let events: Vec<_> = client
+        .stream_response(request("mock-model"))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert!(matches!(
+        events.as_slice(),
+        [Err(StreamingError::MissingEventType { .. })]
+    ));
+}
+
+#[tokio::test]
+async fn test_lifecycle_routes_poll_resume_cancel_and_delete() {
+    let target = testing::response("resp_bg", vec![Item::assistant_message("Resumed fine.")]);
+    let mut queued = target.clone();
+    queued.status = ResponseStatus::InProgress;
+    queued.output.clear();
+
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockReply::response(target.clone()).disconnect_after(4))
+        .enqueue(MockReply::response(target.clone()))
+        .enqueue(MockReply::response(queued.clone()))
+        .enqueue(MockReply::response(target.clone()))
+        .enqueue(MockReply::response(queued))
+        .enqueue(MockReply::deleted("resp_bg"));
+    let client = client(&server, RetryPolicy::none());
+
+    let mut stream = client
+        .stream_response_resumable(
+            request("mock-model"),
+            ResumeOptions::new().reconnect_delay(Duration::from_millis(1)),
+        )
+        .await
+        .unwrap();
+    let mut accumulator = StreamAccumulator::new();
+    while let Some(event) = stream.next().await {
+        accumulator.apply(&event.unwrap()).unwrap();
+    }
+    let resumed = accumulator.into_response().unwrap();
+    assert!(matches!(
+        resumed.output.as_slice(),
+        [Item::Message { content, .. }]
+            if content == &vec![MessageContent::output_text("Resumed fine.")]
+    ));
+    assert!(stream.violations().is_empty());
+
+    let options = PollOptions::new().interval(Duration::from_millis(1));
+    let polled = client.wait_for_completion("resp_bg", options).await.unwrap();
+    assert_eq!(polled.status, ResponseStatus::Completed);
+
+    let cancelled = client.cancel_response("resp_bg").await.unwrap();
+    assert_eq!(cancelled.status, ResponseStatus::InProgress);
+    assert!(client.delete_response("resp_bg").await.unwrap().deleted);
+
+    assert_eq!(
+        server.calls(),
+        [
+            "POST /v1/responses",
+            "GET /v1/responses/resp_bg?stream=true&starting_after=3",
+            "GET /v1/responses/resp_bg",
+            "GET /v1/responses/resp_bg",
+            "POST /v1/responses/resp_bg/cancel",
+            "DELETE /v1/responses/resp_bg",
+        ]
+    );
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(server.pending_replies(), 0);
+}
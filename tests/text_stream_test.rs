@@ -0,0 +1,133 @@
+mod common;
+
+use common::{response_json, sse_body, text_message_events};
+use futures::StreamExt;
+use mockito::Server;
+use openresponses_rust::{
+    CreateResponseBody, ResponseStreamExt, StreamingClient, StreamingError, StreamingEvent,
+    TextChunk, TextStreamOptions,
+};
+use serde_json::{Value, json};
+
+fn event_stream(values: Vec<Value>) -> impl futures::Stream<Item = Result<StreamingEvent, StreamingError>> + Unpin {
+    futures::stream::iter(
+        values
+            .into_iter()
+            .map(|value| Ok(serde_json::from_value(value).unwrap())),
+    )
+}
+
+#[tokio::test]
+async fn test_text_chunks_yield_deltas_and_completion() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(&text_message_events("resp_123", &["Hel", "lo"]), true))
+        .create_async()
+        .await;
+
+    let client = StreamingClient::with_base_url("test-key", server.url());
+    let chunks: Vec<_> = client
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap()
+        .text_chunks()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(
+        chunks[0],
+        TextChunk::TextDelta {
+            output_index: 0,
+            content_index: 0,
+            text: "Hel".to_string()
+        }
+    );
+    assert!(matches!(&chunks[1], TextChunk::TextDelta { text, .. } if text == "lo"));
+    assert!(matches!(&chunks[2], TextChunk::Completed(response) if response.id == "resp_123"));
+}
+
+#[tokio::test]
+async fn test_text_chunks_report_tool_calls_by_call_id() {
+    let mut done = response_json("resp_1", "completed");
+    done["output"] = json!([]);
+    let stream = event_stream(vec![
+        json!({"type": "response.output_item.added", "sequence_number": 1, "output_index": 0,
+            "item": {"type": "function_call", "id": "fc_1", "call_id": "call_1", "name": "get_weather", "arguments": ""}}),
+        json!({"type": "response.function_call_arguments.delta", "sequence_number": 2, "item_id": "fc_1",
+            "output_index": 0, "delta": "{}"}),
+        json!({"type": "response.function_call_arguments.done", "sequence_number": 3, "item_id": "fc_1",
+            "output_index": 0, "arguments": "{}"}),
+        json!({"type": "response.output_item.done", "sequence_number": 4, "output_index": 0,
+            "item": {"type": "function_call", "id": "fc_1", "call_id": "call_1", "name": "get_weather", "arguments": "{}"}}),
+        json!({"type": "response.reasoning_summary_text.delta", "sequence_number": 5, "item_id": "rs_1",
+            "output_index": 1, "summary_index": 0, "delta": "thinking"}),
+        json!({"type": "acme.progress", "sequence_number": 6, "percent": 50}),
+        json!({"type": "response.incomplete", "sequence_number": 7, "response": done}),
+    ]);
+
+    let chunks: Vec<_> = stream.text_chunks().map(Result::unwrap).collect().await;
+
+    assert_eq!(
+        chunks[..3],
+        [
+            TextChunk::ToolCallStarted {
+                output_index: 0,
+                call_id: "call_1".to_string(),
+                name: "get_weather".to_string()
+            },
+            TextChunk::ToolCallArgumentsDelta {
+                output_index: 0,
+                call_id: "call_1".to_string(),
+                delta: "{}".to_string()
+            },
+            TextChunk::ToolCallFinished {
+                output_index: 0,
+                call_id: "call_1".to_string(),
+                name: "get_weather".to_string(),
+                arguments: "{}".to_string()
+            },
+        ]
+    );
+    assert!(matches!(&chunks[3], TextChunk::ReasoningSummaryDelta { text, .. } if text == "thinking"));
+    assert!(matches!(&chunks[4], TextChunk::Incomplete(_)));
+    assert_eq!(chunks.len(), 5);
+}
+
+#[tokio::test]
+async fn test_text_chunks_can_include_lifecycle_and_unknown_events() {
+    let values = text_message_events("resp_1", &["Hi"]);
+    let total = values.len();
+    let mut with_unknown = values.clone();
+    with_unknown.insert(1, json!({"type": "acme.progress", "sequence_number": 99}));
+
+    let unknown_only: Vec<_> = event_stream(with_unknown.clone())
+        .text_chunks_with(TextStreamOptions::new().include_unknown(true))
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert!(matches!(
+        &unknown_only[0],
+        TextChunk::Event(StreamingEvent::Unknown { event_type, .. }) if event_type == "acme.progress"
+    ));
+    assert_eq!(unknown_only.len(), 3);
+
+    let everything: Vec<_> = event_stream(with_unknown)
+        .text_chunks_with(
+            TextStreamOptions::new()
+                .include_lifecycle(true)
+                .include_unknown(true),
+        )
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(everything.len(), total + 1);
+    assert!(matches!(
+        &everything[0],
+        TextChunk::Event(StreamingEvent::ResponseCreated { .. })
+    ));
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use common::{response_json, sse_body, text_message_events};
+use futures::StreamExt;
+use mockito::{Matcher, Server};
+use openresponses_rust::{
+    Client, ClientError, CreateResponseBody, RequestOptions, ResumeOptions, StreamingClient,
+    StreamingError, StreamingEvent,
+};
+use std::io::Write;
+use std::time::Duration;
+
+fn stalled_sse_body(before_stall: String, after_stall: String) -> impl Fn(&mut dyn Write) -> std::io::Result<()> {
+    move |writer| {
+        writer.write_all(before_stall.as_bytes())?;
+        writer.flush()?;
+        std::thread::sleep(Duration::from_millis(400));
+        writer.write_all(after_stall.as_bytes())
+    }
+}
+
+#[tokio::test]
+async fn test_request_timeout_override_applies_to_create_response() {
+    let mut server = Server::new_async().await;
+    let body = response_json("resp_123", "completed").to_string();
+    let _mock = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_chunked_body(stalled_sse_body(String::new(), body))
+        .create_async()
+        .await;
+
+    let client = Client::with_base_url("test-key", server.url());
+    let error = client
+        .create_response_with_options(
+            CreateResponseBody::default(),
+            RequestOptions::new().timeout(Duration::from_millis(50)),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ClientError::HttpError(ref error) if error.is_timeout()));
+}
+
+#[tokio::test]
+async fn test_stream_idle_timeout_is_reported() {
+    let mut server = Server::new_async().await;
+    let events = text_message_events("resp_123", &["Hel", "lo"]);
+    let _mock = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_chunked_body(stalled_sse_body(
+            sse_body(&events[..2], false),
+            sse_body(&events[2..], true),
+        ))
+        .create_async()
+        .await;
+
+    let client = StreamingClient::builder("test-key")
+        .base_url(server.url())
+        .idle_timeout(Duration::from_millis(100))
+        .build();
+    let results: Vec<_> = client
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 3);
+    assert!(results[..2].iter().all(Result::is_ok));
+    assert!(matches!(
+        results[2],
+        Err(StreamingError::IdleTimeout { timeout }) if timeout == Duration::from_millis(100)
+    ));
+}
+
+#[tokio::test]
+async fn test_per_call_idle_timeout_overrides_client_default() {
+    let mut server = Server::new_async().await;
+    let events = text_message_events("resp_123", &["Hel", "lo"]);
+    let _mock = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_chunked_body(stalled_sse_body(
+            sse_body(&events[..2], false),
+            sse_body(&events[2..], true),
+        ))
+        .create_async()
+        .await;
+
+    let client = StreamingClient::builder("test-key")
+        .base_url(server.url())
+        .idle_timeout(Duration::from_millis(100))
+        .build();
+    let results: Vec<_> = client
+        .stream_response_with_options(
+            CreateResponseBody::default(),
+            RequestOptions::new().idle_timeout(Duration::from_secs(5)),
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert!(results.iter().all(Result::is_ok));
+    assert!(matches!(results.last(), Some(Ok(StreamingEvent::Done))));
+}
+
+#[tokio::test]
+async fn test_resumable_stream_reconnects_after_idle_timeout() {
+    let mut server = Server::new_async().await;
+    let events = text_message_events("resp_123", &["Hel", "lo"]);
+    let _create = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_chunked_body(stalled_sse_body(
+            sse_body(&events[..2], false),
+            sse_body(&events[2..], true),
+        ))
+        .create_async()
+        .await;
+    let resume = server
+        .mock("GET", "/v1/responses/resp_123")
+        .match_query(Matcher::UrlEncoded("starting_after".into(), "2".into()))
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(&events[2..], true))
+        .create_async()
+        .await;
+
+    let client = StreamingClient::builder("test-key")
+        .base_url(server.url())
+        .idle_timeout(Duration::from_millis(100))
+        .build();
+    let results: Vec<_> = client
+        .stream_response_resumable(
+            CreateResponseBody::default(),
+            ResumeOptions::new().reconnect_delay(Duration::from_millis(1)),
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    resume.assert_async().await;
+    assert!(results.iter().all(Result::is_ok));
+    assert!(matches!(results.last(), Some(Ok(StreamingEvent::Done))));
+}
@@ -0,0 +1,181 @@
+use openresponses_rust::{
+    CancellationToken, ExecuteOptions, FunctionCallOutputStatus, FunctionOutput, Item,
+    MessageContent, MessageRole, Tool, ToolError, ToolRegistry,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[derive(Deserialize)]
+struct WeatherArgs {
+    city: String,
+}
+
+#[derive(Serialize)]
+struct Weather {
+    city: String,
+    temperature_c: i32,
+}
+
+fn registry() -> ToolRegistry {
+    ToolRegistry::new()
+        .register(
+            Tool::function("get_weather").with_description("Current weather"),
+            |args: WeatherArgs| async move {
+                if args.city.is_empty() {
+                    return Err("city must not be empty".to_string());
+                }
+                Ok(Weather {
+                    city: args.city,
+                    temperature_c: 21,
+                })
+            },
+        )
+        .register(Tool::function("ping"), |_: Value| async {
+            Ok::<_, String>("pong")
+        })
+}
+
+fn call(call_id: &str, name: &str, arguments: &str) -> Item {
+    Item::FunctionCall {
+        id: None,
+        call_id: call_id.to_string(),
+        name: name.to_string(),
+        arguments: arguments.to_string(),
+        status: None,
+    }
+}
+
+fn output(item: &Item) -> (&str, Value) {
+    match item {
+        Item::FunctionCallOutput {
+            call_id,
+            output: FunctionOutput::Text(text),
+            ..
+        } => (call_id, serde_json::from_str(text).unwrap_or(json!(text))),
+        other => panic!("expected function call output, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_registry_dispatches_typed_handlers_by_name() {
+    let registry = registry();
+    let names: Vec<_> = registry
+        .tools()
+        .into_iter()
+        .map(|tool| match tool {
+            Tool::Function { name, .. } => name,
+            other => panic!("unexpected tool {other:?}"),
+        })
+        .collect();
+    assert_eq!(names, ["get_weather", "ping"]);
+
+    let outputs = registry
+        .execute_all(&[
+            Item::assistant_message("Checking"),
+            call("call_1", "get_weather", r#"{"city": "Seoul"}"#),
+            call("call_2", "ping", ""),
+        ])
+        .await;
+
+    assert_eq!(outputs.len(), 2);
+    assert_eq!(
+        output(&outputs[0]),
+        ("call_1", json!({"city": "Seoul", "temperature_c": 21}))
+    );
+    assert_eq!(output(&outputs[1]), ("call_2", json!("pong")));
+    assert!(
+        registry
+            .execute(&Item::Message {
+                id: None,
+                status: None,
+                role: MessageRole::User,
+                content: vec![MessageContent::input_text("hi")],
+            })
+            .await
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_failures_become_error_outputs() {
+    let registry = registry();
+
+    let cases = [
+        (call("call_1", "get_time", "{}"), "unknown_tool"),
+        (call("call_2", "get_weather", r#"{"town": "Seoul"}"#), "invalid_arguments"),
+        (call("call_3", "get_weather", "{not json"), "invalid_arguments"),
+        (call("call_4", "get_weather", r#"{"city": ""}"#), "tool_error"),
+    ];
+    for (item, kind) in cases {
+        let output_item = registry.execute(&item).await.unwrap();
+        let (_, value) = output(&output_item);
+        assert_eq!(value["error"]["type"], kind, "{value}");
+        assert!(value["error"]["message"].as_str().unwrap().contains('`'));
+    }
+
+    assert!(matches!(
+        registry.invoke("get_weather", r#"{"city": ""}"#).await,
+        Err(ToolError::Failed { ref message, .. }) if message == "city must not be empty"
+    ));
+}
+
+#[derive(Deserialize)]
+struct SleepArgs {
+    ms: u64,
+}
+
+#[tokio::test]
+async fn test_parallel_execution_is_bounded_and_ordered() {
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let registry = {
+        let (running, peak) = (running.clone(), peak.clone());
+        ToolRegistry::new().register(Tool::function("sleep"), move |args: SleepArgs| {
+            let (running, peak) = (running.clone(), peak.clone());
+            async move {
+                peak.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(args.ms)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok::<_, String>(args.ms)
+            }
+        })
+    };
+    let calls = [
+        call("call_1", "sleep", r#"{"ms": 60}"#),
+        call("call_2", "sleep", r#"{"ms": 10}"#),
+        call("call_3", "sleep", r#"{"ms": 30}"#),
+    ];
+
+    for (options, expected_peak) in [
+        (ExecuteOptions::new(), 3),
+        (ExecuteOptions::new().max_concurrency(2), 2),
+        (ExecuteOptions::new().parallel(false), 1),
+    ] {
+        peak.store(0, Ordering::SeqCst);
+        let outputs = registry.execute_all_with(&calls, &options).await;
+        let outputs: Vec<_> = outputs.iter().map(output).collect();
+        assert_eq!(
+            outputs,
+            [("call_1", json!(60)), ("call_2", json!(10)), ("call_3", json!(30))]
+        );
+        assert_eq!(peak.load(Ordering::SeqCst), expected_peak);
+    }
+
+    let slow = call("call_4", "sleep", r#"{"ms": 5000}"#);
+    let token = CancellationToken::new();
+    token.cancel();
+    for (options, kind) in [
+        (ExecuteOptions::new().timeout(Duration::from_millis(20)), "timeout"),
+        (ExecuteOptions::new().cancel_token(token), "cancelled"),
+    ] {
+        let item = registry.execute_with(&slow, &options).await.unwrap();
+        assert!(matches!(
+            item,
+            Item::FunctionCallOutput { status: Some(FunctionCallOutputStatus::Incomplete), .. }
+        ));
+        assert_eq!(output(&item).1["error"]["type"], kind);
+    }
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::{response_json, sse_body, text_message_events};
+use futures::StreamExt;
+use mockito::{Matcher, Server};
+use openresponses_rust::{
+    Client, CreateResponseBody, ResponsesClient, StreamingClient, StreamingEvent,
+};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_responses_client_creates_and_streams() {
+    let mut server = Server::new_async().await;
+    let create_mock = server
+        .mock("POST", "/v1/responses")
+        .match_header("authorization", "Bearer test-key")
+        .match_body(Matcher::PartialJson(serde_json::json!({"model": "gpt-4o"})))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_123", "completed").to_string())
+        .expect(1)
+        .create_async()
+        .await;
+    let stream_mock = server
+        .mock("POST", "/v1/responses")
+        .match_header("authorization", "Bearer test-key")
+        .match_header("accept", "text/event-stream")
+        .match_body(Matcher::PartialJson(serde_json::json!({"stream": true})))
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(&text_message_events("resp_456", &["Hi"]), true))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = ResponsesClient::with_base_url("test-key", server.url());
+    let request = CreateResponseBody {
+        model: Some("gpt-4o".to_string()),
+        ..Default::default()
+    };
+
+    let response = client.create_response(request.clone()).await.unwrap();
+    assert_eq!(response.id, "resp_123");
+
+    let events: Vec<_> = client
+        .stream_response(request)
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert!(events.iter().all(Result::is_ok));
+    assert!(matches!(events.last(), Some(Ok(StreamingEvent::Done))));
+
+    create_mock.assert_async().await;
+    stream_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_request_timeout_does_not_cut_streams_short() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_chunked_body(|writer| {
+            let events = text_message_events("resp_123", &["Hel", "lo"]);
+            writer.write_all(sse_body(&events[..2], false).as_bytes())?;
+            writer.flush()?;
+            std::thread::sleep(Duration::from_millis(200));
+            writer.write_all(sse_body(&events[2..], true).as_bytes())
+        })
+        .create_async()
+        .await;
+
+    let client = ResponsesClient::builder("test-key")
+        .base_url(server.url())
+        .timeout(Duration::from_millis(50))
+        .build();
+
+    let events: Vec<_> = client
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert!(events.iter().all(Result::is_ok));
+    assert!(matches!(events.last(), Some(Ok(StreamingEvent::Done))));
+}
+
+#[tokio::test]
+async fn test_wrappers_built_from_one_client_share_configuration() {
+    let mut server = Server::new_async().await;
+    let _get_mock = server
+        .mock("GET", "/v1/responses/resp_123")
+        .match_header("authorization", "Bearer shared-key")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response_json("resp_123", "completed").to_string())
+        .create_async()
+        .await;
+    let _stream_mock = server
+        .mock("POST", "/v1/responses")
+        .match_header("authorization", "Bearer shared-key")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(&text_message_events("resp_123", &["Hi"]), true))
+        .create_async()
+        .await;
+
+    let shared = ResponsesClient::with_base_url("shared-key", server.url());
+    let client = Client::from(shared.clone());
+    let streaming = StreamingClient::from(shared);
+
+    let response = client.get_response("resp_123").await.unwrap();
+    assert_eq!(response.id, "resp_123");
+
+    let mut stream = streaming
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap();
+    assert!(matches!(
+        stream.next().await,
+        Some(Ok(StreamingEvent::ResponseCreated { .. }))
+    ));
+}
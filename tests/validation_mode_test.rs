@@ -0,0 +1,190 @@
+mod common;
+
+use common::{sse_body, text_message_events};
+use futures::StreamExt;
+use mockito::{Server, ServerGuard};
+use openresponses_rust::{
+    CreateResponseBody, StreamingClient, StreamingError, StreamingEvent, ValidationMode,
+};
+use std::sync::{Arc, Mutex};
+
+/// A stream the way some OpenAI-compatible servers send it: no SSE event
+/// names and no `response.content_part.added` before the text deltas.
+async fn off_spec_server() -> ServerGuard {
+    let mut events = text_message_events("resp_123", &["Hel", "lo"]);
+    events.retain(|event| event["type"] != "response.content_part.added");
+    let mut body: String = events
+        .iter()
+        .map(|event| format!("data: {event}\n\n"))
+        .collect();
+    body.push_str("data: [DONE]\n\n");
+
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(body)
+        .create_async()
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn test_strict_mode_fails_on_first_violation() {
+    let server = off_spec_server().await;
+    let client = StreamingClient::with_base_url("test-key", server.url());
+
+    let mut stream = client
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(StreamingError::MissingEventType { ref body_type })) if body_type == "response.created"
+    ));
+}
+
+#[tokio::test]
+async fn test_warn_mode_streams_and_reports_violations() {
+    let server = off_spec_server().await;
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let client = StreamingClient::builder("test-key")
+        .base_url(server.url())
+        .validation_mode(ValidationMode::Warn)
+        .on_violation({
+            let seen = seen.clone();
+            move |violation| seen.lock().unwrap().push(violation.clone())
+        })
+        .build();
+
+    let stream = client
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap();
+    let report = stream.report();
+    let events: Vec<_> = stream.map(Result::unwrap).collect().await;
+
+    assert!(matches!(events.last(), Some(StreamingEvent::Done)));
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, StreamingEvent::ResponseCompleted { .. })));
+
+    let violations = report.violations();
+    assert_eq!(*seen.lock().unwrap(), violations);
+    assert!(violations
+        .iter()
+        .any(|violation| violation.event_type == "response.created"
+            && violation.sequence_number == Some(1)
+            && violation.message.contains("missing a matching SSE event name")));
+    assert!(violations
+        .iter()
+        .any(|violation| violation.event_type == "response.output_text.delta"
+            && violation.message.contains("before `response.content_part.added`")));
+}
+
+#[tokio::test]
+async fn test_off_mode_skips_validation() {
+    let server = off_spec_server().await;
+    let client = StreamingClient::builder("test-key")
+        .base_url(server.url())
+        .validation_mode(ValidationMode::Off)
+        .build();
+
+    let mut stream = client
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap();
+    let mut count = 0;
+    while let Some(event) = stream.next().await {
+        event.unwrap();
+        count += 1;
+    }
+
+    assert!(count > 0);
+    assert!(stream.violations().is_empty());
+}
+
+#[tokio::test]
+async fn test_warn_mode_reports_duplicate_and_out_of_order_sequence_numbers() {
+    let mut events = text_message_events("resp_123", &["Hel", "lo"]);
+    let mut stale = events[3].clone();
+    stale["sequence_number"] = serde_json::json!(3);
+    events.insert(4, events[3].clone());
+    events.insert(6, stale);
+
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(&events, true))
+        .create_async()
+        .await;
+
+    let client = StreamingClient::builder("test-key")
+        .base_url(server.url())
+        .validation_mode(ValidationMode::Warn)
+        .build();
+    let mut stream = client
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap();
+    while let Some(event) = stream.next().await {
+        event.unwrap();
+    }
+
+    let messages: Vec<_> = stream
+        .violations()
+        .into_iter()
+        .map(|violation| violation.message)
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "Duplicate sequence number 4",
+            "Sequence number 3 arrived after 5",
+        ]
+    );
+    assert_eq!(
+        stream.last_sequence_number(),
+        events.last().unwrap()["sequence_number"].as_i64().map(|n| n as i32)
+    );
+}
+
+#[tokio::test]
+async fn test_warn_mode_reports_a_sequence_gap_once() {
+    let mut events = text_message_events("resp_123", &["Hel", "lo"]);
+    for event in &mut events[1..] {
+        event["sequence_number"] = serde_json::json!(event["sequence_number"].as_i64().unwrap() + 1);
+    }
+
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/v1/responses")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse_body(&events, true))
+        .create_async()
+        .await;
+
+    let client = StreamingClient::builder("test-key")
+        .base_url(server.url())
+        .validation_mode(ValidationMode::Warn)
+        .build();
+    let mut stream = client
+        .stream_response(CreateResponseBody::default())
+        .await
+        .unwrap();
+    while let Some(event) = stream.next().await {
+        event.unwrap();
+    }
+
+    let messages: Vec<_> = stream
+        .violations()
+        .into_iter()
+        .map(|violation| violation.message)
+        .collect();
+    assert_eq!(messages, ["Sequence numbers 2..3 were skipped after 1"]);
+}